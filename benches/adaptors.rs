@@ -0,0 +1,188 @@
+//! Compares the adaptors against the equivalent hand-written `map`/`filter`
+//! closures, over iterators with specialized internal iteration (`BTreeMap`
+//! and `Chain`).
+
+use std::{collections::BTreeMap, hint::black_box};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use itermap::IterMap;
+
+const LEN: u64 = 10_000;
+
+fn map() -> BTreeMap<u64, u64> {
+    (0..LEN).map(|i| (i, i * 2)).collect()
+}
+
+fn chain() -> (BTreeMap<u64, u64>, BTreeMap<u64, u64>) {
+    let half = LEN / 2;
+
+    (
+        (0..half).map(|i| (i, i * 2)).collect(),
+        (half..LEN).map(|i| (i, i * 2)).collect(),
+    )
+}
+
+// `count` after `map` is exactly the hand-written chain being compared against.
+#[allow(clippy::suspicious_map)]
+fn map_keys(c: &mut Criterion) {
+    let map = map();
+    let mut group = c.benchmark_group("map_keys");
+
+    group.bench_function("fold/itermap", |b| {
+        b.iter(|| {
+            black_box(&map)
+                .iter()
+                .map_keys(|k| k + 1)
+                .fold(0, |acc, (k, v)| acc ^ k ^ v)
+        })
+    });
+    group.bench_function("fold/std", |b| {
+        b.iter(|| {
+            black_box(&map)
+                .iter()
+                .map(|(k, v)| (k + 1, v))
+                .fold(0, |acc, (k, v)| acc ^ k ^ v)
+        })
+    });
+
+    group.bench_function("count/itermap", |b| {
+        b.iter(|| black_box(&map).iter().map_keys(|k| k + 1).count())
+    });
+    group.bench_function("count/std", |b| {
+        b.iter(|| black_box(&map).iter().map(|(k, v)| (k + 1, v)).count())
+    });
+
+    group.bench_function("nth/itermap", |b| {
+        b.iter(|| {
+            black_box(&map)
+                .iter()
+                .map_keys(|k| k + 1)
+                .nth(LEN as usize / 2)
+        })
+    });
+    group.bench_function("nth/std", |b| {
+        b.iter(|| {
+            black_box(&map)
+                .iter()
+                .map(|(k, v)| (k + 1, v))
+                .nth(LEN as usize / 2)
+        })
+    });
+
+    group.finish();
+}
+
+fn map_values(c: &mut Criterion) {
+    let (front, back) = chain();
+    let mut group = c.benchmark_group("map_values");
+
+    group.bench_function("chain_fold/itermap", |b| {
+        b.iter(|| {
+            black_box(&front)
+                .iter()
+                .chain(black_box(&back))
+                .map_values(|v| v + 1)
+                .fold(0, |acc, (k, v)| acc ^ k ^ v)
+        })
+    });
+    group.bench_function("chain_fold/std", |b| {
+        b.iter(|| {
+            black_box(&front)
+                .iter()
+                .chain(black_box(&back))
+                .map(|(k, v)| (k, v + 1))
+                .fold(0, |acc, (k, v)| acc ^ k ^ v)
+        })
+    });
+
+    group.bench_function("rfold/itermap", |b| {
+        b.iter(|| {
+            black_box(&front)
+                .iter()
+                .chain(black_box(&back))
+                .map_values(|v| v + 1)
+                .rfold(0, |acc, (k, v)| acc ^ k ^ v)
+        })
+    });
+    group.bench_function("rfold/std", |b| {
+        b.iter(|| {
+            black_box(&front)
+                .iter()
+                .chain(black_box(&back))
+                .map(|(k, v)| (k, v + 1))
+                .rfold(0, |acc, (k, v)| acc ^ k ^ v)
+        })
+    });
+
+    group.finish();
+}
+
+fn swap(c: &mut Criterion) {
+    let map = map();
+    let mut group = c.benchmark_group("swap");
+
+    group.bench_function("fold/itermap", |b| {
+        b.iter(|| {
+            black_box(&map)
+                .iter()
+                .swap()
+                .fold(0, |acc, (v, k)| acc ^ k ^ v)
+        })
+    });
+    group.bench_function("fold/std", |b| {
+        b.iter(|| {
+            black_box(&map)
+                .iter()
+                .map(|(k, v)| (v, k))
+                .fold(0, |acc, (v, k)| acc ^ k ^ v)
+        })
+    });
+
+    group.bench_function("nth/itermap", |b| {
+        b.iter(|| black_box(&map).iter().swap().nth(LEN as usize / 2))
+    });
+    group.bench_function("nth/std", |b| {
+        b.iter(|| {
+            black_box(&map)
+                .iter()
+                .map(|(k, v)| (v, k))
+                .nth(LEN as usize / 2)
+        })
+    });
+
+    group.finish();
+}
+
+fn filter(c: &mut Criterion) {
+    let map = map();
+    let mut group = c.benchmark_group("filter_keys");
+
+    group.bench_function("fold/itermap", |b| {
+        b.iter(|| {
+            black_box(&map)
+                .iter()
+                .filter_keys(|k| *k % 3 == 0)
+                .fold(0, |acc, (k, v)| acc ^ k ^ v)
+        })
+    });
+    group.bench_function("fold/std", |b| {
+        b.iter(|| {
+            black_box(&map)
+                .iter()
+                .filter(|(k, _)| *k % 3 == 0)
+                .fold(0, |acc, (k, v)| acc ^ k ^ v)
+        })
+    });
+
+    group.bench_function("count/itermap", |b| {
+        b.iter(|| black_box(&map).iter().filter_keys(|k| *k % 3 == 0).count())
+    });
+    group.bench_function("count/std", |b| {
+        b.iter(|| black_box(&map).iter().filter(|(k, _)| *k % 3 == 0).count())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, map_keys, map_values, swap, filter);
+criterion_main!(benches);
@@ -0,0 +1,166 @@
+//! The derive macro for [`itermap::KeyValue`]. Use it through the `derive`
+//! feature of `itermap`, rather than depending on this crate directly.
+//!
+//! [`itermap::KeyValue`]: https://docs.rs/itermap/latest/itermap/trait.KeyValue.html
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Field, Fields, Member};
+
+/// Derives `itermap::KeyValue` for a struct, and `itermap::FromKeyValue` if
+/// it only has a key and a value field.
+///
+/// Mark the key field with `#[kv(key)]`. The value is the field marked with
+/// `#[kv(value)]`, or the only other field if there are just two. Any other
+/// fields are dropped by `into_parts`. There's nothing to set them to in
+/// `from_parts`, so structs with other fields don't get `FromKeyValue`.
+#[proc_macro_derive(KeyValue, attributes(kv))]
+pub fn derive_key_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Role {
+    Key,
+    Value,
+    Other,
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        Data::Enum(data) => {
+            return Err(Error::new(
+                data.enum_token.span,
+                "`KeyValue` can only be derived for structs",
+            ))
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span,
+                "`KeyValue` can only be derived for structs",
+            ))
+        }
+    };
+
+    let roles = fields.iter().map(role).collect::<syn::Result<Vec<_>>>()?;
+    let key = find(fields, &roles, Role::Key)?
+        .ok_or_else(|| Error::new(input.ident.span(), "mark the key field with `#[kv(key)]`"))?;
+    let value =
+        match find(fields, &roles, Role::Value)? {
+            Some(value) => value,
+            None if fields.len() == 2 => 1 - key,
+            None if fields.len() == 1 => {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "add a value field, since the only field is the key",
+                ))
+            }
+            None => return Err(Error::new(
+                input.ident.span(),
+                "mark the value field with `#[kv(value)]`, since there are more than two fields",
+            )),
+        };
+
+    let members: Vec<Member> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(i.into()),
+        })
+        .collect();
+    let (key_member, value_member) = (&members[key], &members[value]);
+    let key_ty = &fields.iter().nth(key).unwrap().ty;
+    let value_ty = &fields.iter().nth(value).unwrap().ty;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let from_key_value = (fields.len() == 2).then(|| {
+        quote! {
+            impl #impl_generics ::itermap::FromKeyValue for #name #ty_generics #where_clause {
+                fn from_parts(key: Self::Key, value: Self::Value) -> Self {
+                    Self {
+                        #key_member: key,
+                        #value_member: value,
+                    }
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::itermap::KeyValue for #name #ty_generics #where_clause {
+            type Key = #key_ty;
+            type Value = #value_ty;
+
+            fn with_key<R>(&self, f: impl ::core::ops::FnOnce(&Self::Key) -> R) -> R {
+                f(&self.#key_member)
+            }
+
+            fn with_value<R>(&self, f: impl ::core::ops::FnOnce(&Self::Value) -> R) -> R {
+                f(&self.#value_member)
+            }
+
+            fn into_parts(self) -> (Self::Key, Self::Value) {
+                (self.#key_member, self.#value_member)
+            }
+        }
+
+        #from_key_value
+    })
+}
+
+/// Reads the `#[kv(...)]` attribute on a field, if any.
+fn role(field: &Field) -> syn::Result<Role> {
+    let mut role = Role::Other;
+
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("kv")) {
+        attr.parse_nested_meta(|meta| {
+            let found = if meta.path.is_ident("key") {
+                Role::Key
+            } else if meta.path.is_ident("value") {
+                Role::Value
+            } else {
+                return Err(meta.error("expected `key` or `value`"));
+            };
+
+            if role != Role::Other {
+                return Err(meta.error("a field can only be marked once"));
+            }
+
+            role = found;
+            Ok(())
+        })?;
+    }
+
+    Ok(role)
+}
+
+/// Finds the only field with the role, erroring if more than one has it.
+fn find(fields: &Fields, roles: &[Role], role: Role) -> syn::Result<Option<usize>> {
+    let mut found = None;
+
+    for (i, field) in fields.iter().enumerate() {
+        if roles[i] != role {
+            continue;
+        }
+
+        if found.is_some() {
+            let attr = if role == Role::Key { "key" } else { "value" };
+            return Err(Error::new(
+                field.span(),
+                format!("only one field can be marked `#[kv({attr})]`"),
+            ));
+        }
+
+        found = Some(i);
+    }
+
+    Ok(found)
+}
@@ -0,0 +1,175 @@
+//! The iterator adaptors returned by [`IterMap`], [`IntoPairs`], and
+//! [`TryIterMap`], for naming them in struct fields and function signatures.
+//!
+//! # Stability
+//!
+//! These types, their generic parameters, and the traits they implement are
+//! part of the public API, and only change in a breaking release. Their fields
+//! are private; use methods like [`MapKeys::get_ref`] and
+//! [`MapKeys::into_inner`] to get at the underlying iterator.
+//!
+//! The same types are also available, undocumented, at the crate root, where
+//! they were before this module existed.
+//!
+//! # Performance
+//!
+//! Adaptors that map, filter, or swap items forward `fold`, `rfold`, `nth`,
+//! `nth_back`, `count`, and `last` to the underlying iterator, so its own
+//! optimized versions are used. `try_fold` and `try_rfold` can't be
+//! overridden on stable Rust, since their signatures use the unstable `Try`
+//! trait, so they fall back to calling `next` and `next_back`.
+//!
+//! # Naming pipelines
+//!
+//! Closures can't be named, but closures that don't capture anything coerce
+//! to function pointers. The aliases here, like [`MapKeysFn`] and
+//! [`OwnedKeys`], use function pointers so pipelines can be stored or
+//! returned without boxing or `impl Trait`.
+//!
+//! ```
+//! use std::array;
+//!
+//! use itermap::{adaptors::MapValuesFn, IterMap};
+//! # use pretty_assertions::assert_eq;
+//!
+//! type Doubled = MapValuesFn<array::IntoIter<(char, u32), 2>, u32, u32>;
+//!
+//! fn doubled(pairs: [(char, u32); 2]) -> Doubled {
+//!     pairs.into_iter().map_values(|v| v * 2)
+//! }
+//!
+//! assert_eq!(vec![('a', 2), ('b', 4)], doubled([('a', 1), ('b', 2)]).collect::<Vec<_>>());
+//! ```
+//!
+//! [`IterMap`]: crate::IterMap
+//! [`IntoPairs`]: crate::IntoPairs
+//! [`TryIterMap`]: crate::TryIterMap
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::ToOwned;
+
+pub use crate::{
+    display::{DisplayMap, DisplayTable},
+    fill::FillMissingKeys,
+    filter::{FilterKeys, FilterValues},
+    lift::{KeyBy, Pairs, PairsMut, ValueBy},
+    map::{MapKeys, MapValues},
+    nested::FlattenNested,
+    result::{TryFilterKeys, TryFilterValues, TryMapKeys, TryMapValues, TrySwap},
+    sorted_by_key::{CheckSorted, SortedByKey, VerifySorted},
+    swap::Swap,
+};
+
+#[cfg(feature = "alloc")]
+pub use crate::sorted::Sorted;
+#[cfg(feature = "std")]
+pub use crate::{
+    changes::{ChangesByKey, ChangesByKeyEq},
+    intern::InternKeys,
+    normalize::NormalizeKeys,
+    scan::{DeltasByKey, ScanByKey},
+};
+
+/// [`MapKeys`] with a function pointer, mapping keys `K` to `L`.
+pub type MapKeysFn<I, K, L> = MapKeys<I, fn(K) -> L>;
+
+/// [`MapValues`] with a function pointer, mapping values `V` to `W`.
+pub type MapValuesFn<I, V, W> = MapValues<I, fn(V) -> W>;
+
+/// [`FilterKeys`] with a function pointer, filtering on keys `K`.
+pub type FilterKeysFn<I, K> = FilterKeys<I, fn(&K) -> bool>;
+
+/// [`FilterValues`] with a function pointer, filtering on values `V`.
+pub type FilterValuesFn<I, V> = FilterValues<I, fn(&V) -> bool>;
+
+/// Converts borrowed keys `&'a K` to owned ones, like `&str` to `String`.
+///
+/// Made with `.map_keys(|k| k.to_owned())`, or `.map_keys(ToOwned::to_owned)`.
+///
+/// Requires the `alloc` feature.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::{hash_map, HashMap};
+///
+/// use itermap::{adaptors::OwnedKeys, IterMap};
+/// # use pretty_assertions::assert_eq;
+///
+/// struct Config {
+///     values: HashMap<String, u32>,
+/// }
+///
+/// impl Config {
+///     // Yields `(String, &u32)`.
+///     fn keys_owned(&self) -> OwnedKeys<'_, hash_map::Iter<'_, String, u32>, String> {
+///         self.values.iter().map_keys(|k| k.to_owned())
+///     }
+/// }
+///
+/// let config = Config {
+///     values: HashMap::from([(String::from("port"), 8080)]),
+/// };
+///
+/// let owned: Vec<(String, &u32)> = config.keys_owned().collect();
+/// assert_eq!(vec![(String::from("port"), &8080)], owned);
+/// ```
+#[cfg(feature = "alloc")]
+pub type OwnedKeys<'a, I, K> = MapKeys<I, fn(&'a K) -> <K as ToOwned>::Owned>;
+
+/// Converts borrowed values `&'a V` to owned ones, like `&str` to `String`.
+///
+/// Made with `.map_values(|v| v.to_owned())`, or
+/// `.map_values(ToOwned::to_owned)`.
+///
+/// Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub type OwnedValues<'a, I, V> = MapValues<I, fn(&'a V) -> <V as ToOwned>::Owned>;
+
+/// Converts borrowed keys and values, like those from [`HashMap::iter`], to
+/// owned ones.
+///
+/// Made with `.map_keys(|k| k.to_owned()).map_values(|v| v.to_owned())`,
+/// with the intermediate [`OwnedKeys`] named so its closure is coerced too.
+///
+/// Requires the `alloc` feature.
+///
+/// [`HashMap::iter`]: std::collections::HashMap::iter
+#[cfg(feature = "alloc")]
+pub type OwnedPairs<'a, I, K, V> = OwnedValues<'a, OwnedKeys<'a, I, K>, V>;
+
+#[cfg(test)]
+mod test {
+    use std::collections::{btree_map, BTreeMap};
+
+    use pretty_assertions::assert_eq;
+
+    use super::FilterKeysFn;
+    #[cfg(feature = "alloc")]
+    use super::{OwnedKeys, OwnedPairs};
+    use crate::IterMap;
+
+    #[test]
+    fn function_pointer() {
+        fn odd(map: BTreeMap<u8, char>) -> FilterKeysFn<btree_map::IntoIter<u8, char>, u8> {
+            map.into_iter().filter_keys(|k| k % 2 == 1)
+        }
+
+        let map = BTreeMap::from([(1, 'a'), (2, 'b'), (3, 'c')]);
+        assert_eq!(vec![(1, 'a'), (3, 'c')], odd(map).collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn owned_pairs() {
+        fn owned(
+            map: &BTreeMap<String, Vec<u8>>,
+        ) -> OwnedPairs<'_, btree_map::Iter<'_, String, Vec<u8>>, String, Vec<u8>> {
+            let keys: OwnedKeys<'_, _, String> = map.iter().map_keys(|k| k.to_owned());
+            keys.map_values(|v| v.to_owned())
+        }
+
+        let map = BTreeMap::from([(String::from("a"), vec![1]), (String::from("b"), vec![2])]);
+        assert_eq!(map, owned(&map).collect());
+    }
+}
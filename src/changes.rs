@@ -0,0 +1,256 @@
+use alloc::collections::BTreeMap;
+use core::{
+    fmt,
+    hash::Hash,
+    iter::{FusedIterator, Iterator},
+};
+use std::collections::HashMap;
+
+use crate::KeyValue;
+
+/// An `Iterator` that only yields items whose value changed from the last
+/// value yielded for the same key.
+///
+/// See: [`IterMap::changes_by_key`] and [`IterMap::changes_by_key_by`]
+///
+/// [`IterMap::changes_by_key`]: crate::IterMap::changes_by_key
+/// [`IterMap::changes_by_key_by`]: crate::IterMap::changes_by_key_by
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct ChangesByKey<I, K, V, F> {
+    iter: I,
+    eq: F,
+    /// The last value yielded for each key, and when the key was last seen.
+    last: HashMap<K, (V, u64)>,
+    /// Keys by when they were last seen, only kept with `max_keys`.
+    order: BTreeMap<u64, K>,
+    max_keys: Option<usize>,
+    tick: u64,
+}
+
+/// [`ChangesByKey`] comparing values with [`PartialEq`]. Returned by
+/// [`IterMap::changes_by_key`].
+///
+/// [`IterMap::changes_by_key`]: crate::IterMap::changes_by_key
+pub type ChangesByKeyEq<I, K, V> = ChangesByKey<I, K, V, fn(&V, &V) -> bool>;
+
+impl<I, K, V, F> ChangesByKey<I, K, V, F> {
+    pub(crate) fn new(iter: I, eq: F) -> Self {
+        Self {
+            iter,
+            eq,
+            last: HashMap::new(),
+            order: BTreeMap::new(),
+            max_keys: None,
+            tick: 0,
+        }
+    }
+
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items in this adaptor.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the adaptor, returning the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I, K, V, F> ChangesByKey<I, K, V, F>
+where
+    K: Eq + Hash + Clone,
+{
+    /// Remembers at most `max_keys` keys, forgetting the least recently seen
+    /// key when there are more. A forgotten key's next item is always
+    /// yielded, like the first item for a key.
+    pub fn max_keys(mut self, max_keys: usize) -> Self {
+        self.order = self
+            .last
+            .iter()
+            .map(|(key, (_, seen))| (*seen, key.clone()))
+            .collect();
+        self.max_keys = Some(max_keys);
+        self.evict();
+        self
+    }
+
+    fn evict(&mut self) {
+        let Some(max_keys) = self.max_keys else {
+            return;
+        };
+
+        while self.last.len() > max_keys {
+            let Some((_, key)) = self.order.pop_first() else {
+                break;
+            };
+            self.last.remove(&key);
+        }
+    }
+}
+
+impl<I, K, V, F> fmt::Debug for ChangesByKey<I, K, V, F>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChangesByKey")
+            .field("iter", &self.iter)
+            .field("max_keys", &self.max_keys)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I, K, V, F> Iterator for ChangesByKey<I, K, V, F>
+where
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: Eq + Hash + Clone,
+    V: Clone,
+    F: FnMut(&V, &V) -> bool,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.iter.next()?;
+            self.tick += 1;
+
+            let changed = match item.with_key(|key| self.last.get_mut(key)) {
+                Some((last, seen)) => {
+                    if self.max_keys.is_some() {
+                        let key = self
+                            .order
+                            .remove(seen)
+                            .expect("every remembered key is in `order` with `max_keys`");
+                        self.order.insert(self.tick, key);
+                    }
+                    *seen = self.tick;
+
+                    let changed = !item.with_value(|value| (self.eq)(last, value));
+                    if changed {
+                        *last = item.with_value(V::clone);
+                    }
+                    changed
+                }
+                None => {
+                    let key = item.with_key(K::clone);
+                    if self.max_keys.is_some() {
+                        self.order.insert(self.tick, key.clone());
+                    }
+                    self.last
+                        .insert(key, (item.with_value(V::clone), self.tick));
+                    self.evict();
+                    true
+                }
+            };
+
+            if changed {
+                return Some(item);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<I, K, V, F> FusedIterator for ChangesByKey<I, K, V, F>
+where
+    I: FusedIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: Eq + Hash + Clone,
+    V: Clone,
+    F: FnMut(&V, &V) -> bool,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::IterMap;
+
+    fn telemetry() -> Vec<(&'static str, i32)> {
+        vec![
+            ("temp", 20),
+            ("fan", 1),
+            ("temp", 20),
+            ("fan", 1),
+            ("temp", 21),
+            ("fan", 1),
+            ("temp", 20),
+        ]
+    }
+
+    #[test]
+    fn changes() {
+        let changes: Vec<_> = telemetry().into_iter().changes_by_key().collect();
+
+        assert_eq!(
+            vec![("temp", 20), ("fan", 1), ("temp", 21), ("temp", 20)],
+            changes
+        );
+    }
+
+    #[test]
+    fn tolerance() {
+        let readings = vec![
+            ("a", 1.0),
+            ("a", 1.04),
+            ("a", 1.08),
+            ("a", 1.09),
+            ("a", 1.0),
+        ];
+
+        let changes: Vec<_> = readings
+            .into_iter()
+            .changes_by_key_by(|last, value: &f64| (last - value).abs() < 0.05)
+            .collect();
+
+        // Compared to the last value yielded, so drift is still reported.
+        assert_eq!(vec![("a", 1.0), ("a", 1.08), ("a", 1.0)], changes);
+    }
+
+    #[test]
+    fn max_keys() {
+        let readings = vec![("a", 1), ("b", 1), ("a", 1), ("c", 1), ("b", 1), ("a", 1)];
+
+        let changes: Vec<_> = readings
+            .clone()
+            .into_iter()
+            .changes_by_key()
+            .max_keys(2)
+            .collect();
+        // "b" is forgotten when "c" is seen, since "a" was seen after it, then
+        // "a" is forgotten when "b" is seen again.
+        assert_eq!(
+            vec![("a", 1), ("b", 1), ("c", 1), ("b", 1), ("a", 1)],
+            changes
+        );
+
+        let changes: Vec<_> = readings.into_iter().changes_by_key().max_keys(0).collect();
+        assert_eq!(6, changes.len());
+    }
+
+    #[test]
+    fn max_keys_after_start() {
+        let mut changes = vec![("a", 1), ("b", 1), ("c", 1), ("a", 1), ("c", 1)]
+            .into_iter()
+            .changes_by_key();
+
+        changes.by_ref().take(3).for_each(drop);
+        let rest: Vec<_> = changes.max_keys(1).collect();
+
+        // Only "c", the most recently seen, is remembered, until "a" is seen.
+        assert_eq!(vec![("a", 1), ("c", 1)], rest);
+    }
+}
@@ -0,0 +1,402 @@
+use core::fmt::{self, Write};
+
+use crate::KeyValue;
+
+/// Formats an `Iterator` over two-element tuples (like `(K, V)`) like a map,
+/// `{k: v, ...}`, without collecting it.
+///
+/// The iterator is cloned each time this is formatted. [`fmt::Display`] uses
+/// the `Display` implementations of the keys and values, and [`fmt::Debug`]
+/// uses their `Debug` implementations.
+///
+/// See: [`IterMap::display_map`]
+///
+/// [`IterMap::display_map`]: crate::IterMap::display_map
+#[must_use = "this does nothing unless formatted"]
+#[derive(Clone)]
+pub struct DisplayMap<I> {
+    iter: I,
+    open: &'static str,
+    close: &'static str,
+    separator: &'static str,
+    key_value_separator: &'static str,
+    quote_keys: bool,
+    quote_values: bool,
+    limit: Option<usize>,
+}
+
+impl<I> DisplayMap<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        Self {
+            iter,
+            open: "{",
+            close: "}",
+            separator: ", ",
+            key_value_separator: ": ",
+            quote_keys: false,
+            quote_values: false,
+            limit: None,
+        }
+    }
+
+    /// Sets what's written before and after the entries. Defaults to `{` and
+    /// `}`.
+    pub fn delimiters(mut self, open: &'static str, close: &'static str) -> Self {
+        self.open = open;
+        self.close = close;
+        self
+    }
+
+    /// Sets what's written between entries. Defaults to `, `.
+    pub fn separator(mut self, separator: &'static str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Sets what's written between each key and value. Defaults to `: `.
+    pub fn key_value_separator(mut self, separator: &'static str) -> Self {
+        self.key_value_separator = separator;
+        self
+    }
+
+    /// Wraps keys in double quotes, escaping any `"` or `\` in them. Only
+    /// applies to [`fmt::Display`].
+    pub fn quote_keys(mut self, quote: bool) -> Self {
+        self.quote_keys = quote;
+        self
+    }
+
+    /// Wraps values in double quotes, escaping any `"` or `\` in them. Only
+    /// applies to [`fmt::Display`].
+    pub fn quote_values(mut self, quote: bool) -> Self {
+        self.quote_values = quote;
+        self
+    }
+
+    /// Only writes the first `limit` entries, followed by `...` if there were
+    /// more.
+    pub fn truncate_after(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    fn write<K, V, F>(&self, f: &mut fmt::Formatter<'_>, mut write_entry: F) -> fmt::Result
+    where
+        I: Iterator + Clone,
+        I::Item: KeyValue<Key = K, Value = V>,
+        F: FnMut(&mut fmt::Formatter<'_>, K, V) -> fmt::Result,
+    {
+        f.write_str(self.open)?;
+
+        for (i, (k, v)) in self.iter.clone().map(KeyValue::into_parts).enumerate() {
+            if i > 0 {
+                f.write_str(self.separator)?;
+            }
+
+            if self.limit == Some(i) {
+                f.write_str("...")?;
+                break;
+            }
+
+            write_entry(f, k, v)?;
+        }
+
+        f.write_str(self.close)
+    }
+}
+
+impl<I, K, V> fmt::Display for DisplayMap<I>
+where
+    I: Iterator + Clone,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: fmt::Display,
+    V: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, |f, k, v| {
+            write_maybe_quoted(f, &k, self.quote_keys)?;
+            f.write_str(self.key_value_separator)?;
+            write_maybe_quoted(f, &v, self.quote_values)
+        })
+    }
+}
+
+impl<I, K, V> fmt::Debug for DisplayMap<I>
+where
+    I: Iterator + Clone,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, |f, k, v| {
+            write!(f, "{k:?}")?;
+            f.write_str(self.key_value_separator)?;
+            write!(f, "{v:?}")
+        })
+    }
+}
+
+/// Formats an `Iterator` over two-element tuples (like `(K, V)`) as an aligned,
+/// two-column table, one entry per line, without collecting it.
+///
+/// The iterator is cloned twice each time this is formatted: once to measure
+/// the width of the keys, and once to write the entries. Widths are measured
+/// in `char`s.
+///
+/// See: [`IterMap::display_table`]
+///
+/// [`IterMap::display_table`]: crate::IterMap::display_table
+#[must_use = "this does nothing unless formatted"]
+#[derive(Clone)]
+pub struct DisplayTable<I> {
+    iter: I,
+    headers: Option<(&'static str, &'static str)>,
+    separator: &'static str,
+    limit: Option<usize>,
+}
+
+impl<I> DisplayTable<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        Self {
+            iter,
+            headers: None,
+            separator: "  ",
+            limit: None,
+        }
+    }
+
+    /// Writes a header row, followed by a line under it.
+    pub fn headers(mut self, key: &'static str, value: &'static str) -> Self {
+        self.headers = Some((key, value));
+        self
+    }
+
+    /// Sets what's written between the columns. Defaults to two spaces.
+    pub fn separator(mut self, separator: &'static str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Only writes the first `limit` entries, followed by a `...` line if
+    /// there were more.
+    pub fn truncate_after(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+impl<I, K, V> fmt::Display for DisplayTable<I>
+where
+    I: Iterator + Clone,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: fmt::Display,
+    V: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let limit = self.limit.unwrap_or(usize::MAX);
+
+        let (mut key_width, mut value_width) =
+            self.headers.map_or((0, 0), |(k, v)| (width(&k), width(&v)));
+
+        for (k, v) in self.iter.clone().take(limit).map(KeyValue::into_parts) {
+            key_width = key_width.max(width(&k));
+            value_width = value_width.max(width(&v));
+        }
+
+        let mut first = true;
+        let mut line = |f: &mut fmt::Formatter<'_>| {
+            if !core::mem::take(&mut first) {
+                f.write_char('\n')?;
+            }
+            Ok(())
+        };
+
+        if let Some((k, v)) = self.headers {
+            line(f)?;
+            write_row(f, &k, &v, key_width, self.separator)?;
+
+            line(f)?;
+            write_repeated(f, '-', key_width)?;
+            write_repeated(f, '-', width(&self.separator))?;
+            write_repeated(f, '-', value_width)?;
+        }
+
+        for (i, (k, v)) in self.iter.clone().map(KeyValue::into_parts).enumerate() {
+            line(f)?;
+
+            if i == limit {
+                f.write_str("...")?;
+                break;
+            }
+
+            write_row(f, &k, &v, key_width, self.separator)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<I> fmt::Debug for DisplayTable<I>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DisplayTable")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
+    }
+}
+
+fn write_row<K, V>(
+    f: &mut fmt::Formatter<'_>,
+    k: &K,
+    v: &V,
+    key_width: usize,
+    separator: &str,
+) -> fmt::Result
+where
+    K: fmt::Display,
+    V: fmt::Display,
+{
+    write!(f, "{k}")?;
+    write_repeated(f, ' ', key_width.saturating_sub(width(k)))?;
+    f.write_str(separator)?;
+    write!(f, "{v}")
+}
+
+fn write_repeated(f: &mut fmt::Formatter<'_>, c: char, n: usize) -> fmt::Result {
+    (0..n).try_for_each(|_| f.write_char(c))
+}
+
+fn write_maybe_quoted<T>(f: &mut fmt::Formatter<'_>, value: &T, quote: bool) -> fmt::Result
+where
+    T: fmt::Display,
+{
+    if !quote {
+        return write!(f, "{value}");
+    }
+
+    f.write_char('"')?;
+    write!(Escaped(f), "{value}")?;
+    f.write_char('"')
+}
+
+/// The number of `char`s `value` is formatted as.
+fn width<T>(value: &T) -> usize
+where
+    T: fmt::Display + ?Sized,
+{
+    let mut width = Width(0);
+    // Writing to `Width` never fails.
+    let _ = write!(width, "{value}");
+    width.0
+}
+
+struct Width(usize);
+
+impl Write for Width {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+/// Escapes `"` and `\` written through it.
+struct Escaped<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+impl Write for Escaped<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for part in s.split_inclusive(['"', '\\']) {
+            match part.strip_suffix(['"', '\\']) {
+                Some(rest) => {
+                    self.0.write_str(rest)?;
+                    self.0.write_char('\\')?;
+                    self.0.write_str(&part[rest.len()..])?;
+                }
+                None => self.0.write_str(part)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::IterMap;
+
+    fn map() -> BTreeMap<&'static str, &'static str> {
+        BTreeMap::from([("a", "1"), ("bbb", "22"), ("cc", "3")])
+    }
+
+    #[test]
+    fn display_map() {
+        let map = map();
+
+        assert_eq!(
+            "{a: 1, bbb: 22, cc: 3}",
+            map.iter().display_map().to_string()
+        );
+        assert_eq!(
+            r#"{"a": "1", "bbb": "22", "cc": "3"}"#,
+            format!("{:?}", map.iter().display_map())
+        );
+        assert_eq!(
+            r#"["a"=1; "bbb"=22; ...]"#,
+            map.iter()
+                .display_map()
+                .delimiters("[", "]")
+                .separator("; ")
+                .key_value_separator("=")
+                .quote_keys(true)
+                .truncate_after(2)
+                .to_string()
+        );
+        assert_eq!("{}", map.iter().filter(|_| false).display_map().to_string());
+        assert_eq!(
+            "{a: 1, bbb: 22, cc: 3}",
+            map.iter().display_map().truncate_after(3).to_string()
+        );
+    }
+
+    #[test]
+    fn quoting() {
+        let items = [("say \"hi\"", r"C:\temp")];
+
+        assert_eq!(
+            r#"{"say \"hi\"": "C:\\temp"}"#,
+            items
+                .iter()
+                .copied()
+                .display_map()
+                .quote_keys(true)
+                .quote_values(true)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn display_table() {
+        let map = map();
+
+        assert_eq!(
+            "a    1\nbbb  22\ncc   3",
+            map.iter().display_table().to_string()
+        );
+        assert_eq!(
+            "name | value\n------------\na    | 1\nbbb  | 22\n...",
+            map.iter()
+                .display_table()
+                .headers("name", "value")
+                .separator(" | ")
+                .truncate_after(2)
+                .to_string()
+        );
+        assert_eq!("", map.iter().filter(|_| false).display_table().to_string());
+    }
+}
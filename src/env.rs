@@ -0,0 +1,311 @@
+//! Turning environment variables (or any `Iterator` over string pairs) into
+//! nested configuration keys.
+//!
+//! [`EnvKeys`] keeps only the variables with a given prefix, strips it, and
+//! splits what's left on a separator into a key path, optionally lowercasing
+//! it. It works on any `Iterator` over `(K, V)`, or other [`KeyValue`] items,
+//! where `K: AsRef<str>`, so [`std::env::vars()`] can be swapped for a fixed
+//! list in tests.
+//!
+//! # Example
+//!
+//! ```
+//! # use std::collections::BTreeMap;
+//! #
+//! use itermap::env::EnvKeys;
+//! # use pretty_assertions::assert_eq;
+//!
+//! // In real code, this would be `std::env::vars()`.
+//! let vars = vec![
+//!     ("APP_DB__HOST", "localhost"),
+//!     ("APP_DB__PORT", "5432"),
+//!     ("APP_DEBUG", "true"),
+//!     ("HOME", "/root"),
+//! ];
+//!
+//! let config: BTreeMap<_, _> = EnvKeys::new("APP_")
+//!     .separator("__")
+//!     .lowercase()
+//!     .dotted(vars)
+//!     .collect();
+//!
+//! assert_eq!(
+//!     BTreeMap::from([
+//!         (String::from("db.host"), "localhost"),
+//!         (String::from("db.port"), "5432"),
+//!         (String::from("debug"), "true"),
+//!     ]),
+//!     config,
+//! );
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::iter::FusedIterator;
+
+use crate::KeyValue;
+
+/// How to turn environment variable names into key paths.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug, Clone, Default)]
+pub struct EnvKeys {
+    prefix: String,
+    separator: Option<String>,
+    lowercase: bool,
+}
+
+impl EnvKeys {
+    /// Only keep variables whose names start with `prefix`, and strip it from
+    /// the keys. An empty prefix keeps all variables.
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Split the keys on `separator` (like `__`) into key paths. Empty parts
+    /// are dropped. Without a separator, each key path has one part.
+    pub fn separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = Some(separator.into());
+        self
+    }
+
+    /// Lowercase the keys.
+    pub fn lowercase(mut self) -> Self {
+        self.lowercase = true;
+        self
+    }
+
+    /// Turns the variables into `(Vec<String>, V)` pairs, where the key is
+    /// the key path.
+    pub fn paths<I, K, V>(self, vars: I) -> EnvPaths<I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: KeyValue<Key = K, Value = V>,
+        K: AsRef<str>,
+    {
+        EnvPaths {
+            iter: vars.into_iter(),
+            keys: self,
+        }
+    }
+
+    /// Turns the variables into `(String, V)` pairs, where the key is the key
+    /// path joined with `.`, like `db.host`.
+    pub fn dotted<I, K, V>(self, vars: I) -> EnvDotted<I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: KeyValue<Key = K, Value = V>,
+        K: AsRef<str>,
+    {
+        EnvDotted(self.paths(vars))
+    }
+
+    /// Returns the key path for a variable name, if it should be kept.
+    fn path(&self, name: &str) -> Option<Vec<String>> {
+        let name = name.strip_prefix(self.prefix.as_str())?;
+
+        let path: Vec<String> = match &self.separator {
+            Some(separator) => name
+                .split(separator.as_str())
+                .filter(|part| !part.is_empty())
+                .map(|part| self.fold(part))
+                .collect(),
+            None if name.is_empty() => Vec::new(),
+            None => Vec::from([self.fold(name)]),
+        };
+
+        (!path.is_empty()).then_some(path)
+    }
+
+    fn fold(&self, part: &str) -> String {
+        if self.lowercase {
+            part.to_lowercase()
+        } else {
+            part.to_string()
+        }
+    }
+}
+
+/// Environment variables as `(Vec<String>, V)` key path pairs.
+///
+/// See: [`EnvKeys::paths`]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Debug, Clone)]
+pub struct EnvPaths<I> {
+    iter: I,
+    keys: EnvKeys,
+}
+
+impl<I, K, V> Iterator for EnvPaths<I>
+where
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: AsRef<str>,
+{
+    type Item = (Vec<String>, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let keys = &self.keys;
+        self.iter.by_ref().find_map(|item| {
+            let path = item.with_key(|k| keys.path(k.as_ref()))?;
+            Some((path, item.into_parts().1))
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<I, K, V> FusedIterator for EnvPaths<I>
+where
+    I: FusedIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: AsRef<str>,
+{
+}
+
+/// Environment variables as `(String, V)` pairs, where the key is the key path
+/// joined with `.`.
+///
+/// See: [`EnvKeys::dotted`]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Debug, Clone)]
+pub struct EnvDotted<I>(EnvPaths<I>);
+
+impl<I, K, V> Iterator for EnvDotted<I>
+where
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: AsRef<str>,
+{
+    type Item = (String, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(path, v)| (path.join("."), v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<I, K, V> FusedIterator for EnvDotted<I>
+where
+    I: FusedIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: AsRef<str>,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::EnvKeys;
+
+    fn vars() -> Vec<(String, String)> {
+        [
+            ("APP_NAME", "demo"),
+            ("APP_DB__HOST", "localhost"),
+            ("APP_DB____REPLICA__HOST", "replica"),
+            ("APP_", "empty"),
+            ("APP__", "only separator"),
+            ("PATH", "/bin"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+    }
+
+    #[test]
+    fn paths() {
+        let paths: Vec<_> = EnvKeys::new("APP_").separator("__").paths(vars()).collect();
+
+        assert_eq!(
+            vec![
+                (vec!["NAME".to_string()], "demo".to_string()),
+                (vec!["DB".into(), "HOST".into()], "localhost".into()),
+                (
+                    vec!["DB".into(), "REPLICA".into(), "HOST".into()],
+                    "replica".into()
+                ),
+                (vec!["_".into()], "only separator".into()),
+            ],
+            paths
+        );
+    }
+
+    #[test]
+    fn without_separator() {
+        let keys: Vec<_> = EnvKeys::new("APP_")
+            .lowercase()
+            .dotted(vars())
+            .map(|(k, _)| k)
+            .collect();
+
+        assert_eq!(vec!["name", "db__host", "db____replica__host", "_"], keys);
+    }
+
+    #[test]
+    fn no_prefix() {
+        let keys: Vec<_> = EnvKeys::new("")
+            .separator("_")
+            .lowercase()
+            .dotted(vars())
+            .map(|(k, _)| k)
+            .collect();
+
+        assert_eq!(
+            vec![
+                "app.name",
+                "app.db.host",
+                "app.db.replica.host",
+                "app",
+                "app",
+                "path"
+            ],
+            keys
+        );
+    }
+
+    #[test]
+    fn borrowed() {
+        let vars = vars();
+        let dotted: Vec<_> = EnvKeys::new("APP_DB")
+            .separator("__")
+            .dotted(&vars)
+            .map(|(k, v)| (k, v.as_str()))
+            .collect();
+
+        assert_eq!(
+            vec![
+                ("HOST".to_string(), "localhost"),
+                ("REPLICA.HOST".to_string(), "replica"),
+            ],
+            dotted
+        );
+    }
+
+    #[test]
+    fn real_environment() {
+        // Only this test reads or writes this variable.
+        std::env::set_var("ITERMAP_TEST_ENV__REAL__VAR", "set");
+
+        let paths: Vec<_> = EnvKeys::new("ITERMAP_TEST_ENV__")
+            .separator("__")
+            .lowercase()
+            .paths(std::env::vars())
+            .collect();
+
+        assert_eq!(
+            vec![(vec!["real".to_string(), "var".into()], "set".to_string())],
+            paths
+        );
+    }
+}
@@ -0,0 +1,383 @@
+use core::{
+    fmt,
+    iter::{FusedIterator, Iterator},
+};
+
+use crate::KeyValue;
+
+/// An `Iterator` over items sorted by key that yields every key in order,
+/// filling in the keys that are missing.
+///
+/// See: [`IterMap::fill_missing_keys`]
+///
+/// [`IterMap::fill_missing_keys`]: crate::IterMap::fill_missing_keys
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct FillMissingKeys<I, K, V, Fs, Ff> {
+    iter: I,
+    successor: Fs,
+    fill: Ff,
+    /// The last item from `iter`, for filling the keys after it.
+    last: Option<(K, V)>,
+    /// The last key yielded, which may have been filled.
+    cursor: Option<K>,
+    /// The next item from `iter`, once a gap before it has been found.
+    peeked: Option<(K, V)>,
+    end: Option<K>,
+    done: bool,
+}
+
+impl<I, K, V, Fs, Ff> FillMissingKeys<I, K, V, Fs, Ff> {
+    pub(crate) fn new(iter: I, successor: Fs, fill: Ff) -> Self {
+        Self {
+            iter,
+            successor,
+            fill,
+            last: None,
+            cursor: None,
+            peeked: None,
+            end: None,
+            done: false,
+        }
+    }
+
+    /// Stops after `end`, filling any keys up to it after the underlying
+    /// iterator runs out, and dropping any items after it.
+    pub fn until(mut self, end: K) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items in this adaptor.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the adaptor, returning the underlying iterator. An item read
+    /// ahead to find the end of a gap is dropped.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I, K, V, Fs, Ff> FillMissingKeys<I, K, V, Fs, Ff>
+where
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: PartialOrd + Clone,
+    V: Clone,
+{
+    fn past_end(&self, key: &K) -> bool {
+        self.end.as_ref().is_some_and(|end| key > end)
+    }
+
+    fn yield_item(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if self.past_end(&key) {
+            self.done = true;
+            return None;
+        }
+
+        self.cursor = Some(key.clone());
+        self.last = Some((key.clone(), value.clone()));
+        Some((key, value))
+    }
+}
+
+impl<I, K, V, Fs, Ff> Iterator for FillMissingKeys<I, K, V, Fs, Ff>
+where
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: PartialOrd + Clone,
+    V: Clone,
+    Fs: FnMut(&K) -> K,
+    Ff: FnMut(&K, (&K, &V), Option<(&K, &V)>) -> V,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let Some(cursor) = &self.cursor else {
+            let Some((key, value)) = self.iter.next().map(KeyValue::into_parts) else {
+                self.done = true;
+                return None;
+            };
+            return self.yield_item(key, value);
+        };
+
+        if self.peeked.is_none() {
+            self.peeked = self.iter.next().map(KeyValue::into_parts);
+        }
+
+        // Checked before stepping, so a successor that saturates at the last
+        // key still stops.
+        let stop = match &self.end {
+            Some(end) => cursor >= end,
+            None => self.peeked.is_none(),
+        };
+        if stop {
+            self.done = true;
+            return None;
+        }
+
+        let key = (self.successor)(cursor);
+        assert!(
+            key > *cursor,
+            "`successor` must return a key greater than the one it's given",
+        );
+        if self.past_end(&key) {
+            self.done = true;
+            return None;
+        }
+
+        match &self.peeked {
+            // The next item is due (or isn't on the successor's steps).
+            Some((next_key, _)) if *next_key <= key => {
+                let (key, value) = self.peeked.take().expect("matched `Some` above");
+                self.yield_item(key, value)
+            }
+            // A gap before the next item.
+            Some((next_key, next_value)) => {
+                let (last_key, last_value) = self
+                    .last
+                    .as_ref()
+                    .expect("an item was yielded before the cursor was set");
+                let value = (self.fill)(&key, (last_key, last_value), Some((next_key, next_value)));
+                self.cursor = Some(key.clone());
+                Some((key, value))
+            }
+            // Past the last item, filling up to `end`.
+            None if self.end.is_some() => {
+                let (last_key, last_value) = self
+                    .last
+                    .as_ref()
+                    .expect("an item was yielded before the cursor was set");
+                let value = (self.fill)(&key, (last_key, last_value), None);
+                self.cursor = Some(key.clone());
+                Some((key, value))
+            }
+            None => {
+                self.done = true;
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        // Items past `end` are dropped, and any number of keys can be filled.
+        if self.end.is_some() {
+            (0, None)
+        } else {
+            let peeked = usize::from(self.peeked.is_some());
+            (self.iter.size_hint().0.saturating_add(peeked), None)
+        }
+    }
+}
+
+impl<I, K, V, Fs, Ff> FusedIterator for FillMissingKeys<I, K, V, Fs, Ff>
+where
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: PartialOrd + Clone,
+    V: Clone,
+    Fs: FnMut(&K) -> K,
+    Ff: FnMut(&K, (&K, &V), Option<(&K, &V)>) -> V,
+{
+}
+
+impl<I, K, V, Fs, Ff> Clone for FillMissingKeys<I, K, V, Fs, Ff>
+where
+    I: Clone,
+    K: Clone,
+    V: Clone,
+    Fs: Clone,
+    Ff: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            successor: self.successor.clone(),
+            fill: self.fill.clone(),
+            last: self.last.clone(),
+            cursor: self.cursor.clone(),
+            peeked: self.peeked.clone(),
+            end: self.end.clone(),
+            done: self.done,
+        }
+    }
+}
+
+impl<I, K, V, Fs, Ff> fmt::Debug for FillMissingKeys<I, K, V, Fs, Ff>
+where
+    I: fmt::Debug,
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FillMissingKeys")
+            .field("iter", &self.iter)
+            .field("cursor", &self.cursor)
+            .field("end", &self.end)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::IterMap;
+
+    fn series() -> Vec<(u32, f64)> {
+        vec![(1, 1.0), (2, 2.0), (5, 8.0), (6, 6.0)]
+    }
+
+    #[test]
+    fn defaults() {
+        let filled: Vec<_> = series()
+            .into_iter()
+            .fill_missing_keys(|k| k + 1, |_, _, _| 0.0)
+            .collect();
+
+        assert_eq!(
+            vec![(1, 1.0), (2, 2.0), (3, 0.0), (4, 0.0), (5, 8.0), (6, 6.0)],
+            filled
+        );
+    }
+
+    #[test]
+    fn forward_fill() {
+        let filled: Vec<_> = series()
+            .into_iter()
+            .fill_missing_keys(|k| k + 1, |_, (_, last), _| *last)
+            .collect();
+
+        assert_eq!(
+            vec![(1, 1.0), (2, 2.0), (3, 2.0), (4, 2.0), (5, 8.0), (6, 6.0)],
+            filled
+        );
+    }
+
+    #[test]
+    fn interpolate() {
+        let filled: Vec<_> = series()
+            .into_iter()
+            .fill_missing_keys(
+                |k| k + 1,
+                |k, (k0, v0), next| match next {
+                    Some((k1, v1)) => v0 + (v1 - v0) * f64::from(k - k0) / f64::from(k1 - k0),
+                    None => *v0,
+                },
+            )
+            .collect();
+
+        assert_eq!(
+            vec![(1, 1.0), (2, 2.0), (3, 4.0), (4, 6.0), (5, 8.0), (6, 6.0)],
+            filled
+        );
+    }
+
+    #[test]
+    fn until() {
+        let fill = |_: &u32, _: (&u32, &f64), next: Option<(&u32, &f64)>| {
+            if next.is_some() {
+                0.0
+            } else {
+                -1.0
+            }
+        };
+
+        let extended: Vec<_> = series()
+            .into_iter()
+            .fill_missing_keys(|k| k + 1, fill)
+            .until(8)
+            .collect();
+        assert_eq!(
+            vec![
+                (1, 1.0),
+                (2, 2.0),
+                (3, 0.0),
+                (4, 0.0),
+                (5, 8.0),
+                (6, 6.0),
+                (7, -1.0),
+                (8, -1.0),
+            ],
+            extended
+        );
+
+        let truncated: Vec<_> = series()
+            .into_iter()
+            .fill_missing_keys(|k| k + 1, fill)
+            .until(3)
+            .collect();
+        assert_eq!(vec![(1, 1.0), (2, 2.0), (3, 0.0)], truncated);
+
+        let before_first: Vec<_> = series()
+            .into_iter()
+            .fill_missing_keys(|k| k + 1, fill)
+            .until(0)
+            .collect();
+        assert_eq!(Vec::<(u32, f64)>::new(), before_first);
+    }
+
+    #[test]
+    fn off_step() {
+        // Keys that aren't on the successor's steps are passed through, and
+        // stepping continues from them.
+        let filled: Vec<_> = vec![(0, 'a'), (3, 'b'), (5, 'c')]
+            .into_iter()
+            .fill_missing_keys(|k| k + 2, |_, _, _| '-')
+            .collect();
+
+        assert_eq!(vec![(0, 'a'), (2, '-'), (3, 'b'), (5, 'c')], filled);
+    }
+
+    #[test]
+    fn saturating() {
+        let filled: Vec<_> = vec![(u8::MAX - 2, 'a')]
+            .into_iter()
+            .fill_missing_keys(|k| k.saturating_add(1), |_, _, _| '-')
+            .until(u8::MAX)
+            .collect();
+
+        assert_eq!(vec![(253, 'a'), (254, '-'), (255, '-')], filled);
+
+        let filled: Vec<_> = vec![(u8::MAX - 1, 'a'), (u8::MAX, 'b')]
+            .into_iter()
+            .fill_missing_keys(|k| k.saturating_add(1), |_, _, _| '-')
+            .collect();
+        assert_eq!(vec![(254, 'a'), (255, 'b')], filled);
+    }
+
+    #[test]
+    #[should_panic = "`successor` must return a key greater than the one it's given"]
+    fn successor_not_increasing() {
+        let _ = vec![(1, 'a'), (3, 'b')]
+            .into_iter()
+            .fill_missing_keys(|k| *k, |_, _, _| '-')
+            .count();
+    }
+
+    #[test]
+    fn empty() {
+        let mut filled = Vec::<(u32, u32)>::new()
+            .into_iter()
+            .fill_missing_keys(|k| k + 1, |_, _, _| 0)
+            .until(10);
+
+        assert_eq!(None, filled.next());
+        assert_eq!((0, Some(0)), filled.size_hint());
+    }
+}
@@ -11,7 +11,7 @@ mod test {
     use map_vec::{Map, Set};
     use pretty_assertions::assert_eq;
 
-    use crate::IterMap;
+    use crate::{IntoPairs, IterMap};
 
     #[test]
     fn hash() {
@@ -150,4 +150,74 @@ mod test {
         assert_eq!(expected, filtered);
         println!("{filtered:#?}");
     }
+
+    #[test]
+    fn vec_pairs() {
+        let vec = Vec::from([('a', 'A'), ('b', 'B'), ('c', 'C')]);
+
+        let expected = vec
+            .iter()
+            .find(|(k, _v)| k == &'b')
+            .map(|(k, v)| (k, v))
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        let filtered = vec
+            .iter()
+            .pairs()
+            .filter_keys(|k| k == &&'b')
+            .collect::<Vec<_>>();
+        assert_eq!(expected, filtered);
+        println!("{filtered:#?}");
+
+        let filtered = vec
+            .iter()
+            .pairs()
+            .filter_values(|v| v == &&'B')
+            .collect::<Vec<_>>();
+        assert_eq!(expected, filtered);
+        println!("{filtered:#?}");
+    }
+
+    #[test]
+    fn internal_iteration() {
+        let vec = Vec::from([('a', 1), ('b', 2), ('c', 3), ('d', 4), ('e', 5)]);
+
+        let odd = || vec.clone().into_iter().filter_values(|v| v % 2 == 1);
+        assert_eq!(3, odd().count());
+        assert_eq!(Some(('e', 5)), odd().last());
+        assert_eq!(9, odd().fold(0, |acc, (_, v)| acc + v));
+        assert_eq!(
+            "eca",
+            odd().rfold(String::new(), |mut acc, (k, _)| {
+                acc.push(k);
+                acc
+            })
+        );
+
+        let not_c = || vec.clone().into_iter().filter_keys(|k| k != &'c');
+        assert_eq!(4, not_c().count());
+        assert_eq!(Some(('d', 4)), not_c().nth(2));
+        assert_eq!(Some(('b', 2)), not_c().nth_back(2));
+        assert_eq!(
+            vec![('e', 5), ('d', 4), ('b', 2), ('a', 1)],
+            not_c().rfold(Vec::new(), |mut acc, item| {
+                acc.push(item);
+                acc
+            })
+        );
+    }
+
+    #[test]
+    fn size_hint() {
+        let vec = vec![(1, 1), (2, 2), (3, 3)];
+
+        let keys = vec.clone().into_iter().filter_keys(|k| *k == 1);
+        assert_eq!((0, Some(3)), keys.size_hint());
+        assert_eq!(1, keys.count());
+
+        let mut values = vec.into_iter().filter_values(|v| *v > 1);
+        values.next();
+        assert_eq!((0, Some(1)), values.size_hint());
+    }
 }
@@ -1,6 +1,9 @@
-use core::iter::{DoubleEndedIterator, FusedIterator, Iterator};
+use core::{
+    fmt,
+    iter::{DoubleEndedIterator, FusedIterator, Iterator},
+};
 
-use crate::iter::Iter;
+use crate::KeyValue;
 
 /// Filters items based on the values, the second element of a two-element tuple
 /// (like `(K, V)`).
@@ -9,62 +12,127 @@ use crate::iter::Iter;
 ///
 /// [`IterMap::filter_values`]: crate::IterMap::filter_values
 #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
-#[derive(Debug)]
-pub struct FilterValues<I, P>(Iter<I, P>);
+#[derive(Clone)]
+pub struct FilterValues<I, P> {
+    iter: I,
+    predicate: P,
+}
 
 impl<I, P> FilterValues<I, P> {
     pub(crate) fn new<K, V>(iter: I, predicate: P) -> Self
     where
-        I: Iterator<Item = (K, V)>,
+        I: Iterator,
+        I::Item: KeyValue<Key = K, Value = V>,
         P: FnMut(&V) -> bool,
     {
-        Self(Iter { iter, predicate })
+        Self { iter, predicate }
+    }
+
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items in this adaptor.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the adaptor, returning the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I, P> fmt::Debug for FilterValues<I, P>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FilterValues")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
     }
 }
 
 impl<I, P, K, V> Iterator for FilterValues<I, P>
 where
-    I: Iterator<Item = (K, V)>,
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
     P: FnMut(&V) -> bool,
 {
     type Item = I::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0
-            .iter
+        self.iter
             .by_ref()
-            .find(|item| (self.0.predicate)(&item.1))
+            .find(|item| item.with_value(&mut self.predicate))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.0.iter.size_hint()
+        (0, self.iter.size_hint().1)
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut predicate = self.predicate;
+        self.iter.fold(init, move |acc, item| {
+            if item.with_value(&mut predicate) {
+                f(acc, item)
+            } else {
+                acc
+            }
+        })
+    }
+
+    fn count(self) -> usize {
+        let mut predicate = self.predicate;
+        self.iter
+            .map(|item| usize::from(item.with_value(&mut predicate)))
+            .sum()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.fold(None, |_, item| Some(item))
     }
 }
 
 impl<I, P, K, V> DoubleEndedIterator for FilterValues<I, P>
 where
-    I: DoubleEndedIterator<Item = (K, V)>,
+    I: DoubleEndedIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
     P: FnMut(&V) -> bool,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
-        self.0
-            .iter
+        self.iter
             .by_ref()
             .rev()
-            .find(|item| (self.0.predicate)(&item.1))
+            .find(|item| item.with_value(&mut self.predicate))
     }
-}
 
-impl<I, P, K, V> FusedIterator for FilterValues<I, P>
-where
-    I: FusedIterator<Item = (K, V)>,
-    P: FnMut(&V) -> bool,
-{
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut predicate = self.predicate;
+        self.iter.rfold(init, move |acc, item| {
+            if item.with_value(&mut predicate) {
+                f(acc, item)
+            } else {
+                acc
+            }
+        })
+    }
 }
 
-impl<I, P, K, V> ExactSizeIterator for FilterValues<I, P>
+impl<I, P, K, V> FusedIterator for FilterValues<I, P>
 where
-    I: ExactSizeIterator<Item = (K, V)>,
+    I: FusedIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
     P: FnMut(&V) -> bool,
 {
 }
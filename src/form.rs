@@ -0,0 +1,284 @@
+//! Reading and writing `application/x-www-form-urlencoded` data, like URL
+//! query strings and HTML form submissions, as `Iterator`s over two-element
+//! tuples (like `(K, V)`).
+//!
+//! Use [`encode`] to write pairs and [`parse`] to read them.
+//!
+//! # Example
+//!
+//! ```
+//! use itermap::{form, IterMap};
+//! # use pretty_assertions::assert_eq;
+//!
+//! let query = form::encode([("q", "rust iterators"), ("lang", "en&fr")]).to_string();
+//! assert_eq!("q=rust+iterators&lang=en%26fr", query);
+//!
+//! let params: Vec<_> = form::parse(&query)
+//!     .filter_keys(|k| k == "q")
+//!     .map_values(|v| v.into_owned())
+//!     .collect();
+//! assert_eq!(vec![("q".into(), String::from("rust iterators"))], params);
+//! ```
+
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::{
+    fmt::{self, Write},
+    iter::FusedIterator,
+};
+
+use crate::KeyValue;
+
+/// Percent-encodes the pairs as `application/x-www-form-urlencoded`, like
+/// `a=1&b=2`. The result implements [`fmt::Display`], so nothing is encoded
+/// until it's formatted.
+///
+/// Bytes other than ASCII letters, digits, and `*-._` are percent-encoded,
+/// except spaces, which are written as `+`.
+///
+/// The iterator is cloned each time the result is formatted.
+pub fn encode<I, K, V>(pairs: I) -> Encoded<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    Encoded {
+        iter: pairs.into_iter(),
+    }
+}
+
+/// Pairs percent-encoded as `application/x-www-form-urlencoded`.
+///
+/// See: [`encode`]
+#[must_use = "this does nothing unless formatted"]
+#[derive(Debug, Clone)]
+pub struct Encoded<I> {
+    iter: I,
+}
+
+impl<I, K, V> fmt::Display for Encoded<I>
+where
+    I: Iterator + Clone,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (k, v)) in self.iter.clone().map(KeyValue::into_parts).enumerate() {
+            if i > 0 {
+                f.write_char('&')?;
+            }
+
+            write_encoded(f, k.as_ref())?;
+            f.write_char('=')?;
+            write_encoded(f, v.as_ref())?;
+        }
+
+        Ok(())
+    }
+}
+
+fn is_unreserved(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'*' | b'-' | b'.' | b'_')
+}
+
+fn write_encoded(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    // Start of the current run of bytes that don't need encoding. Those are
+    // all ASCII, so runs always start and end on `char` boundaries.
+    let mut start = 0;
+
+    for (i, b) in s.bytes().enumerate() {
+        if is_unreserved(b) {
+            continue;
+        }
+
+        if start < i {
+            f.write_str(&s[start..i])?;
+        }
+
+        if b == b' ' {
+            f.write_char('+')?;
+        } else {
+            write!(f, "%{b:02X}")?;
+        }
+
+        start = i + 1;
+    }
+
+    if start < s.len() {
+        f.write_str(&s[start..])?;
+    }
+
+    Ok(())
+}
+
+/// Parses `application/x-www-form-urlencoded` data, like a URL query string
+/// (without the leading `?`), into `(key, value)` pairs.
+///
+/// Pairs are separated by `&`, and keys from values by the first `=`. A pair
+/// without an `=` has an empty value, and empty pairs are skipped. `+` is
+/// decoded as a space, and percent-encoded bytes are decoded. Invalid
+/// percent-encodings are left as they are, and invalid UTF-8 is replaced with
+/// `U+FFFD`.
+///
+/// Keys and values are borrowed from `input` unless they had to be decoded.
+pub fn parse(input: &str) -> Parse<'_> {
+    Parse {
+        pairs: input.split('&'),
+    }
+}
+
+/// An `Iterator` over the `(key, value)` pairs in
+/// `application/x-www-form-urlencoded` data.
+///
+/// See: [`parse`]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Debug, Clone)]
+pub struct Parse<'a> {
+    pairs: core::str::Split<'a, char>,
+}
+
+impl<'a> Parse<'a> {
+    fn parse_pair(pair: &'a str) -> (Cow<'a, str>, Cow<'a, str>) {
+        let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+        (decode(k), decode(v))
+    }
+}
+
+impl<'a> Iterator for Parse<'a> {
+    type Item = (Cow<'a, str>, Cow<'a, str>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.pairs
+            .by_ref()
+            .find(|pair| !pair.is_empty())
+            .map(Self::parse_pair)
+    }
+}
+
+impl DoubleEndedIterator for Parse<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.pairs
+            .by_ref()
+            .rev()
+            .find(|pair| !pair.is_empty())
+            .map(Self::parse_pair)
+    }
+}
+
+impl FusedIterator for Parse<'_> {}
+
+fn decode(s: &str) -> Cow<'_, str> {
+    if !s.bytes().any(|b| b == b'+' || b == b'%') {
+        return Cow::Borrowed(s);
+    }
+
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+
+    while let Some((&b, tail)) = rest.split_first() {
+        rest = tail;
+
+        match b {
+            b'+' => bytes.push(b' '),
+            b'%' => match (tail.first().and_then(hex), tail.get(1).and_then(hex)) {
+                (Some(hi), Some(lo)) => {
+                    bytes.push(hi << 4 | lo);
+                    rest = &tail[2..];
+                }
+                _ => bytes.push(b'%'),
+            },
+            b => bytes.push(b),
+        }
+    }
+
+    match String::from_utf8(bytes) {
+        Ok(decoded) => Cow::Owned(decoded),
+        Err(err) => Cow::Owned(String::from_utf8_lossy(err.as_bytes()).into_owned()),
+    }
+}
+
+fn hex(b: &u8) -> Option<u8> {
+    (*b as char).to_digit(16).map(|d| d as u8)
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+
+    use pretty_assertions::assert_eq;
+
+    use super::{encode, parse};
+
+    #[test]
+    fn encode_pairs() {
+        assert_eq!("", encode(Vec::<(&str, &str)>::new()).to_string());
+        assert_eq!(
+            "a=1&b+c=2%2B2%3D4&empty=&%C3%A9=%E2%9C%93",
+            encode([("a", "1"), ("b c", "2+2=4"), ("empty", ""), ("é", "✓")]).to_string()
+        );
+        assert_eq!(
+            "safe=AZaz09*-._&unsafe=%7E%21%2F%3F%23",
+            encode([("safe", "AZaz09*-._"), ("unsafe", "~!/?#")]).to_string()
+        );
+
+        // Any `KeyValue` items, like arrays and references to tuples.
+        assert_eq!("a=1&b=2", encode([["a", "1"], ["b", "2"]]).to_string());
+        let pairs = [("a", String::from("x y"))];
+        assert_eq!("a=x+y", encode(pairs.iter()).to_string());
+    }
+
+    #[test]
+    fn parse_pairs() {
+        let pairs: Vec<_> =
+            parse("a=1&&b+c=2%2B2%3D4&flag&empty=&%C3%A9=%E2%9C%93&x=y=z").collect();
+
+        assert_eq!(
+            vec![
+                ("a".into(), "1".into()),
+                ("b c".into(), "2+2=4".into()),
+                ("flag".into(), "".into()),
+                ("empty".into(), "".into()),
+                ("é".into(), "✓".into()),
+                ("x".into(), "y=z".into()),
+            ],
+            pairs
+        );
+
+        assert!(matches!(pairs[0], (Cow::Borrowed(_), Cow::Borrowed(_))));
+        assert!(matches!(pairs[1], (Cow::Owned(_), Cow::Owned(_))));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let pairs: Vec<_> = parse("bad=%zz%4&utf8=%FF").collect();
+
+        assert_eq!(
+            vec![
+                ("bad".into(), "%zz%4".into()),
+                ("utf8".into(), "\u{FFFD}".into()),
+            ],
+            pairs
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let original = vec![("key with spaces", "ü & ß"), ("%", "100%")];
+
+        let encoded = encode(original.iter().copied()).to_string();
+        let parsed: Vec<_> = parse(&encoded).collect();
+
+        assert_eq!(
+            original,
+            parsed
+                .iter()
+                .map(|(k, v)| (k.as_ref(), v.as_ref()))
+                .collect::<Vec<_>>()
+        );
+
+        let reversed: Vec<_> = parse(&encoded).rev().collect();
+        assert_eq!(parsed.into_iter().rev().collect::<Vec<_>>(), reversed);
+    }
+}
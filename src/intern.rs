@@ -0,0 +1,369 @@
+use alloc::vec::Vec;
+use core::{borrow::Borrow, fmt, hash::Hash, iter::FusedIterator};
+use std::collections::HashMap;
+
+use crate::KeyValue;
+
+/// A type that can be used as an id by an [`Interner`].
+///
+/// Implemented for the unsigned integer types. Implement it for your own types
+/// (like a `struct SymbolId(u32)`) to use those as ids.
+pub trait InternId: Copy {
+    /// Creates an id from an index into the [`Interner`]'s table. Returns
+    /// `None` if the index doesn't fit in this type.
+    fn from_index(index: usize) -> Option<Self>;
+
+    /// Returns the index into the [`Interner`]'s table for this id. Ids that
+    /// don't fit in a `usize` should return `usize::MAX`, which is never a
+    /// valid index, rather than being truncated onto another id's index.
+    fn index(self) -> usize;
+}
+
+macro_rules! impl_intern_id {
+    ($($t:ty),*) => {
+        $(
+            impl InternId for $t {
+                fn from_index(index: usize) -> Option<Self> {
+                    Self::try_from(index).ok()
+                }
+
+                fn index(self) -> usize {
+                    usize::try_from(self).unwrap_or(usize::MAX)
+                }
+            }
+        )*
+    };
+}
+
+impl_intern_id!(u8, u16, u32, u64, usize);
+
+/// Assigns dense ids to keys, in the order they're first seen, and keeps both
+/// an id to key table and a key to id lookup.
+///
+/// Used by [`IterMap::intern_keys`], but may also be used on its own. Pass an
+/// existing `Interner` to [`IterMap::intern_keys_with`] to keep ids stable
+/// across batches.
+///
+/// # Example
+///
+/// ```
+/// use itermap::Interner;
+/// # use pretty_assertions::assert_eq;
+///
+/// let mut interner = Interner::<&str, u32>::new();
+///
+/// assert_eq!(0, interner.intern("a"));
+/// assert_eq!(1, interner.intern("b"));
+/// assert_eq!(0, interner.intern("a"));
+///
+/// assert_eq!(Some(&"b"), interner.resolve(1));
+/// assert_eq!(Some(1), interner.get("b"));
+/// assert_eq!(&["a", "b"], interner.keys());
+/// ```
+///
+/// [`IterMap::intern_keys`]: crate::IterMap::intern_keys
+/// [`IterMap::intern_keys_with`]: crate::IterMap::intern_keys_with
+#[derive(Clone)]
+pub struct Interner<K, Id = u32> {
+    keys: Vec<K>,
+    ids: HashMap<K, Id>,
+}
+
+impl<K, Id> Interner<K, Id> {
+    /// Creates an empty `Interner`.
+    pub fn new() -> Self {
+        Self {
+            keys: Vec::new(),
+            ids: HashMap::new(),
+        }
+    }
+
+    /// The number of distinct keys that have been interned.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if no keys have been interned.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The interned keys, indexed by id.
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+
+    /// Consumes the `Interner`, returning the interned keys, indexed by id.
+    pub fn into_keys(self) -> Vec<K> {
+        self.keys
+    }
+}
+
+impl<K, Id> Interner<K, Id>
+where
+    K: Eq + Hash + Clone,
+    Id: InternId,
+{
+    /// Returns the id for `key`, assigning the next id if it hasn't been seen
+    /// before.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more distinct keys than `Id` can represent.
+    pub fn intern(&mut self, key: K) -> Id {
+        if let Some(id) = self.ids.get(&key) {
+            return *id;
+        }
+
+        let id = Id::from_index(self.keys.len()).expect("ran out of intern ids");
+        self.keys.push(key.clone());
+        self.ids.insert(key, id);
+
+        id
+    }
+
+    /// Returns the id for `key`, if it has been interned.
+    pub fn get<Q>(&self, key: &Q) -> Option<Id>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.ids.get(key).copied()
+    }
+
+    /// Returns the key for `id`, if there is one.
+    pub fn resolve(&self, id: Id) -> Option<&K> {
+        self.keys.get(id.index())
+    }
+
+    /// An iterator over `(Id, &K)` for all interned keys, in id order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Id, &K)> + ExactSizeIterator + '_ {
+        self.keys.iter().enumerate().map(|(index, key)| {
+            let id = Id::from_index(index).expect("ids were assigned from indexes");
+            (id, key)
+        })
+    }
+}
+
+impl<K, Id> Default for Interner<K, Id> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, Id> FromIterator<K> for Interner<K, Id>
+where
+    K: Eq + Hash + Clone,
+    Id: InternId,
+{
+    /// Interns each key in order, so existing id to key tables can be used to
+    /// seed an `Interner`.
+    fn from_iter<T: IntoIterator<Item = K>>(iter: T) -> Self {
+        let mut interner = Self::new();
+        interner.extend(iter);
+        interner
+    }
+}
+
+impl<K, Id> Extend<K> for Interner<K, Id>
+where
+    K: Eq + Hash + Clone,
+    Id: InternId,
+{
+    fn extend<T: IntoIterator<Item = K>>(&mut self, iter: T) {
+        for key in iter {
+            self.intern(key);
+        }
+    }
+}
+
+impl<K, Id> fmt::Debug for Interner<K, Id>
+where
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interner")
+            .field("keys", &self.keys)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Replaces keys, the first element of a two-element tuple (like `(K, V)`),
+/// with dense ids from an [`Interner`].
+///
+/// See: [`IterMap::intern_keys`]
+///
+/// [`IterMap::intern_keys`]: crate::IterMap::intern_keys
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct InternKeys<I, K, Id> {
+    iter: I,
+    interner: Interner<K, Id>,
+}
+
+impl<I, K, Id> InternKeys<I, K, Id> {
+    pub(crate) fn new(iter: I, interner: Interner<K, Id>) -> Self {
+        Self { iter, interner }
+    }
+
+    /// The [`Interner`] holding the keys seen so far.
+    pub fn interner(&self) -> &Interner<K, Id> {
+        &self.interner
+    }
+
+    /// Consumes the adaptor, returning the [`Interner`] holding the keys seen
+    /// so far.
+    pub fn into_interner(self) -> Interner<K, Id> {
+        self.interner
+    }
+
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items in this adaptor.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the adaptor, returning the underlying iterator. The keys
+    /// interned so far are dropped; see [`into_interner`](Self::into_interner).
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I, K, V, Id> Iterator for InternKeys<I, K, Id>
+where
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: Eq + Hash + Clone,
+    Id: InternId,
+{
+    type Item = (Id, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (k, v) = self.iter.next()?.into_parts();
+        Some((self.interner.intern(k), v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, K, V, Id> ExactSizeIterator for InternKeys<I, K, Id>
+where
+    I: ExactSizeIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: Eq + Hash + Clone,
+    Id: InternId,
+{
+}
+
+impl<I, K, V, Id> FusedIterator for InternKeys<I, K, Id>
+where
+    I: FusedIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: Eq + Hash + Clone,
+    Id: InternId,
+{
+}
+
+impl<I, K, Id> fmt::Debug for InternKeys<I, K, Id>
+where
+    I: fmt::Debug,
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InternKeys")
+            .field("iter", &self.iter)
+            .field("interner", &self.interner)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{InternId, Interner};
+    use crate::IterMap;
+
+    #[test]
+    fn out_of_range_id() {
+        let mut interner = Interner::<&str, u64>::new();
+        interner.intern("a");
+
+        assert_eq!(None, interner.resolve(u64::MAX));
+        assert_eq!(None, interner.resolve(1 << 32));
+        assert_eq!(Some(&"a"), interner.resolve(0));
+    }
+
+    #[test]
+    fn intern_keys() {
+        let rows = vec![("b", 1), ("a", 2), ("b", 3), ("c", 4)];
+
+        let mut iter = rows.into_iter().intern_keys::<u32>();
+        let interned: Vec<_> = iter.by_ref().collect();
+
+        assert_eq!(vec![(0, 1), (1, 2), (0, 3), (2, 4)], interned);
+
+        let interner = iter.into_interner();
+        assert_eq!(&["b", "a", "c"], interner.keys());
+        assert_eq!(
+            vec![(0, &"b"), (1, &"a"), (2, &"c")],
+            interner.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn batches() {
+        let interner: Interner<String, u16> = ["x", "y"].into_iter().map(String::from).collect();
+
+        let mut iter = vec![(String::from("y"), 'a'), (String::from("z"), 'b')]
+            .into_iter()
+            .intern_keys_with(interner);
+        let interned: Vec<_> = iter.by_ref().collect();
+
+        assert_eq!(vec![(1, 'a'), (2, 'b')], interned);
+
+        let interner = iter.into_interner();
+        assert_eq!(Some(2), interner.get("z"));
+        assert_eq!(Some(&String::from("x")), interner.resolve(0));
+        assert_eq!(None, interner.resolve(3));
+    }
+
+    #[test]
+    fn custom_id() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Symbol(u32);
+
+        impl InternId for Symbol {
+            fn from_index(index: usize) -> Option<Self> {
+                u32::from_index(index).map(Symbol)
+            }
+
+            fn index(self) -> usize {
+                self.0.index()
+            }
+        }
+
+        let interned: Vec<_> = vec![("a", ()), ("b", ()), ("a", ())]
+            .into_iter()
+            .intern_keys::<Symbol>()
+            .map(|(id, _)| id)
+            .collect();
+
+        assert_eq!(vec![Symbol(0), Symbol(1), Symbol(0)], interned);
+    }
+
+    #[test]
+    #[should_panic(expected = "ran out of intern ids")]
+    fn overflow() {
+        let _ = (0..=256).map(|n| (n, ())).intern_keys::<u8>().count();
+    }
+}
@@ -0,0 +1,297 @@
+/// An item made of a key and a value, like the `(K, V)` tuples yielded by map
+/// iterators.
+///
+/// [`IterMap`] works with any `Iterator` over items implementing this, so
+/// entry types with named fields can be used directly. It's implemented for
+/// two-element tuples, `(K, V)`, references to them, `&(K, V)`, and
+/// two-element arrays, `[T; 2]`. References to tuples have references as their
+/// key and value, so `vec.iter().map_keys(..)` works like
+/// `vec.into_iter().map_keys(..)`, without taking ownership.
+///
+/// Adaptors that only look at items, like [`IterMap::filter_keys`], yield the
+/// original items. Those that change them, like [`IterMap::map_keys`], yield
+/// `(K, V)` tuples.
+///
+/// The key and value are lent out through closures, rather than returned as
+/// references, since a reference to a tuple doesn't contain the references it
+/// has as its key and value. To build items from a key and a value, see
+/// [`FromKeyValue`].
+///
+/// With the `derive` feature, this can be derived for structs. Mark the key
+/// field with `#[kv(key)]`. The value is the field marked with `#[kv(value)]`,
+/// or the only other field if there are just two. Any other fields are dropped
+/// by [`into_parts`](KeyValue::into_parts). Structs with just the two fields
+/// also get [`FromKeyValue`].
+///
+/// ```ignore
+/// #[derive(KeyValue)]
+/// struct Header {
+///     #[kv(key)]
+///     name: String,
+///     value: String,
+/// }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use itermap::{FromKeyValue, IterMap, KeyValue};
+/// # use pretty_assertions::assert_eq;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Header {
+///     name: String,
+///     value: String,
+/// }
+///
+/// impl KeyValue for Header {
+///     type Key = String;
+///     type Value = String;
+///
+///     fn with_key<R>(&self, f: impl FnOnce(&String) -> R) -> R {
+///         f(&self.name)
+///     }
+///
+///     fn with_value<R>(&self, f: impl FnOnce(&String) -> R) -> R {
+///         f(&self.value)
+///     }
+///
+///     fn into_parts(self) -> (String, String) {
+///         (self.name, self.value)
+///     }
+/// }
+///
+/// impl FromKeyValue for Header {
+///     fn from_parts(name: String, value: String) -> Self {
+///         Self { name, value }
+///     }
+/// }
+///
+/// let headers = vec![
+///     Header::from_parts("Host".into(), "example.com".into()),
+///     Header::from_parts("Accept".into(), "text/html".into()),
+/// ];
+///
+/// let names: Vec<&str> = headers.iter().map(|header| header.name.as_str()).collect();
+/// assert_eq!(vec!["Host", "Accept"], names);
+///
+/// let host: Vec<Header> = headers
+///     .into_iter()
+///     .filter_keys(|name| name.eq_ignore_ascii_case("host"))
+///     .collect();
+///
+/// assert_eq!(vec![Header::from_parts("Host".into(), "example.com".into())], host);
+/// ```
+///
+/// [`IterMap`]: crate::IterMap
+/// [`IterMap::filter_keys`]: crate::IterMap::filter_keys
+/// [`IterMap::map_keys`]: crate::IterMap::map_keys
+pub trait KeyValue {
+    /// The type of the key.
+    type Key;
+
+    /// The type of the value.
+    type Value;
+
+    /// Calls `f` with a reference to the key, returning what it returns.
+    fn with_key<R>(&self, f: impl FnOnce(&Self::Key) -> R) -> R;
+
+    /// Calls `f` with a reference to the value, returning what it returns.
+    fn with_value<R>(&self, f: impl FnOnce(&Self::Value) -> R) -> R;
+
+    /// Splits the item into its key and value.
+    fn into_parts(self) -> (Self::Key, Self::Value);
+}
+
+/// A [`KeyValue`] item that can be built from a key and a value.
+///
+/// Implemented for two-element tuples, `(K, V)`, and two-element arrays,
+/// `[T; 2]`, but not for references to tuples, since there's nothing to
+/// borrow them from. Derived along with [`KeyValue`], with the `derive`
+/// feature, for structs with only a key and a value field.
+pub trait FromKeyValue: KeyValue {
+    /// Builds an item from a key and a value.
+    fn from_parts(key: Self::Key, value: Self::Value) -> Self;
+}
+
+impl<K, V> KeyValue for (K, V) {
+    type Key = K;
+    type Value = V;
+
+    fn with_key<R>(&self, f: impl FnOnce(&K) -> R) -> R {
+        f(&self.0)
+    }
+
+    fn with_value<R>(&self, f: impl FnOnce(&V) -> R) -> R {
+        f(&self.1)
+    }
+
+    fn into_parts(self) -> (K, V) {
+        self
+    }
+}
+
+impl<K, V> FromKeyValue for (K, V) {
+    fn from_parts(key: K, value: V) -> Self {
+        (key, value)
+    }
+}
+
+impl<'a, K, V> KeyValue for &'a (K, V) {
+    type Key = &'a K;
+    type Value = &'a V;
+
+    fn with_key<R>(&self, f: impl FnOnce(&&'a K) -> R) -> R {
+        f(&&self.0)
+    }
+
+    fn with_value<R>(&self, f: impl FnOnce(&&'a V) -> R) -> R {
+        f(&&self.1)
+    }
+
+    fn into_parts(self) -> (&'a K, &'a V) {
+        (&self.0, &self.1)
+    }
+}
+
+impl<T> KeyValue for [T; 2] {
+    type Key = T;
+    type Value = T;
+
+    fn with_key<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(&self[0])
+    }
+
+    fn with_value<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(&self[1])
+    }
+
+    fn into_parts(self) -> (T, T) {
+        let [key, value] = self;
+        (key, value)
+    }
+}
+
+impl<T> FromKeyValue for [T; 2] {
+    fn from_parts(key: T, value: T) -> Self {
+        [key, value]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use pretty_assertions::assert_eq;
+
+    use super::{FromKeyValue, KeyValue};
+    use crate::IterMap;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Entry {
+        name: &'static str,
+        count: u32,
+    }
+
+    impl KeyValue for Entry {
+        type Key = &'static str;
+        type Value = u32;
+
+        fn with_key<R>(&self, f: impl FnOnce(&Self::Key) -> R) -> R {
+            f(&self.name)
+        }
+
+        fn with_value<R>(&self, f: impl FnOnce(&Self::Value) -> R) -> R {
+            f(&self.count)
+        }
+
+        fn into_parts(self) -> (Self::Key, Self::Value) {
+            (self.name, self.count)
+        }
+    }
+
+    impl FromKeyValue for Entry {
+        fn from_parts(name: Self::Key, count: Self::Value) -> Self {
+            Self { name, count }
+        }
+    }
+
+    fn entries() -> Vec<Entry> {
+        vec![
+            Entry::from_parts("b", 2),
+            Entry::from_parts("a", 1),
+            Entry::from_parts("c", 3),
+        ]
+    }
+
+    #[test]
+    fn custom() {
+        let filtered: Vec<Entry> = entries().into_iter().filter_values(|c| c > &1).collect();
+        assert_eq!(
+            vec![Entry::from_parts("b", 2), Entry::from_parts("c", 3)],
+            filtered
+        );
+
+        let map: BTreeMap<_, _> = entries()
+            .into_iter()
+            .map_values(|c| c * 10)
+            .swap()
+            .collect();
+        assert_eq!(BTreeMap::from([(10, "a"), (20, "b"), (30, "c")]), map);
+
+        #[cfg(feature = "alloc")]
+        {
+            let sorted: Vec<_> = entries().into_iter().sorted_by_key().collect();
+            assert_eq!(vec![("a", 1), ("b", 2), ("c", 3)], sorted);
+        }
+
+        assert_eq!(Some(3), entries().into_iter().get("c"));
+        assert_eq!(
+            Some(("a", 1)),
+            entries().into_iter().find_by_value(|c| c == &1)
+        );
+        assert_eq!(
+            "{b: 2, a: 1, c: 3}",
+            entries().into_iter().display_map().to_string()
+        );
+    }
+
+    #[test]
+    fn array() {
+        let pairs = vec![["a", "A"], ["b", "B"]];
+
+        let swapped: Vec<_> = pairs.clone().into_iter().swap().collect();
+        assert_eq!(vec![("A", "a"), ("B", "b")], swapped);
+
+        let filtered: Vec<[&str; 2]> = pairs.into_iter().filter_keys(|k| k != &"a").collect();
+        assert_eq!(vec![["b", "B"]], filtered);
+
+        assert_eq!(("k", "v"), ["k", "v"].into_parts());
+        assert_eq!(["k", "v"], <[&str; 2]>::from_parts("k", "v"));
+    }
+
+    #[test]
+    fn tuple_ref() {
+        let pairs = [("a", 1), ("b", 2), ("c", 3)];
+
+        let upper: Vec<_> = pairs.iter().map_keys(|k| k.to_uppercase()).collect();
+        assert_eq!(
+            vec![
+                (String::from("A"), &1),
+                (String::from("B"), &2),
+                (String::from("C"), &3)
+            ],
+            upper
+        );
+
+        let filtered: Vec<&(&str, i32)> = pairs.iter().filter_values(|v| **v > 1).collect();
+        assert_eq!(vec![&("b", 2), &("c", 3)], filtered);
+
+        assert_eq!(Some(&2), pairs.iter().get(&&"b"));
+        assert!(pairs.iter().any_key(|k| *k == &"c"));
+        assert_eq!((&"a", &1), pairs.first().unwrap().into_parts());
+
+        // Still usable after borrowing.
+        assert_eq!(3, pairs.len());
+    }
+}
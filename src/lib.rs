@@ -2,34 +2,143 @@
 //! or any two-element tuple (like `(K, V)`).
 //!
 //! Just import [`IterMap`] to get extra methods on iterators. See that trait
-//! for more documentation and examples.
+//! for more documentation and examples. To use it with your own entry types,
+//! implement [`KeyValue`] for them.
+//!
+//! To turn an iterator over single items, or over references to two-element
+//! tuples like `&(K, V)`, into one over two-element tuples, import
+//! [`IntoPairs`]. References to tuples also work with [`IterMap`] directly.
+//! For iterators over `Result<(K, V), E>`, import [`TryIterMap`]. The adaptor
+//! types they return are in [`adaptors`].
+//!
+//! # Features
+//!
+//! * `std` (default): Enables adaptors that need [`HashMap`], like
+//!   [`IterMap::intern_keys`]. Implies `alloc`.
+//! * `alloc` (default): Enables adaptors that need to buffer items, like
+//!   [`IterMap::sorted_by_key`], and the [`env`](mod@env), [`form`], and
+//!   [`properties`] modules.
+//! * `derive`: Enables `#[derive(KeyValue)]`, for using structs with
+//!   [`IterMap`]. It implements [`KeyValue`], and [`FromKeyValue`] for
+//!   structs with only a key and a value field.
+//! * `serde`: Enables [`SerializeAsMap`] for serializing iterators as maps,
+//!   and [`deserialize_map_with`] for deserializing maps without building one.
+//! * `testing`: Enables assertion macros for comparing iterators ignoring
+//!   order, like [`assert_pairs_eq_unordered!`]. Implies `alloc`.
 //!
 //! [`HashMap`]: std::collections::HashMap
 //! [`BTreeMap`]: std::collections::BTreeMap
 
+#![cfg_attr(not(test), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+// Outside of `std`, only needed to resolve links to `std` types in the
+// documentation.
+#[cfg(any(feature = "std", doc))]
+extern crate std;
+
+pub mod adaptors;
+#[cfg(feature = "std")]
+mod changes;
+mod display;
+#[cfg(feature = "alloc")]
+pub mod env;
+mod fill;
 mod filter;
-mod iter;
+#[cfg(feature = "alloc")]
+pub mod form;
+#[cfg(feature = "std")]
+mod intern;
+mod key_value;
+mod lift;
 mod map;
+mod nested;
+#[cfg(feature = "std")]
+mod normalize;
+#[cfg(feature = "alloc")]
+pub mod properties;
+mod result;
+#[cfg(feature = "std")]
+mod scan;
+#[cfg(feature = "serde")]
+mod serde_map;
+#[cfg(feature = "alloc")]
+mod sorted;
+mod sorted_by_key;
 mod swap;
+#[cfg(feature = "testing")]
+#[doc(hidden)]
+pub mod testing;
 #[cfg(any(test, doctest))]
 mod tests;
 
-use core::iter::Iterator;
+use core::{borrow::Borrow, fmt::Debug, iter::Iterator};
+#[cfg(feature = "std")]
+use core::{hash::Hash, ops::Sub};
+
+pub use self::{
+    key_value::{FromKeyValue, KeyValue},
+    lift::{pairs_from, IntoPairs},
+    result::{ResultValue, TryIterMap},
+    sorted_by_key::{SortedByKey, Unsorted},
+};
+
+#[cfg(feature = "alloc")]
+pub use self::nested::{DenseTable, Unflatten};
+#[cfg(feature = "serde")]
+pub use self::serde_map::{
+    deserialize_map_into, deserialize_map_with, MapEntries, SerializeAsMap, SerializeAsMapOnce,
+};
+#[cfg(feature = "std")]
+pub use self::{
+    intern::{InternId, Interner},
+    normalize::{KeyCollision, Normalized},
+};
+#[cfg(feature = "derive")]
+pub use itermap_derive::KeyValue;
 
+// Documented in `adaptors`, kept here for compatibility.
 #[doc(hidden)]
 pub use self::{
+    display::{DisplayMap, DisplayTable},
+    fill::FillMissingKeys,
     filter::{FilterKeys, FilterValues},
+    lift::{KeyBy, Pairs, PairsMut, ValueBy},
     map::{MapKeys, MapValues},
+    nested::FlattenNested,
+    result::{TryFilterKeys, TryFilterValues, TryMapKeys, TryMapValues, TrySwap},
+    sorted_by_key::{CheckSorted, VerifySorted},
     swap::Swap,
 };
 
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub use self::sorted::Sorted;
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub use self::{
+    changes::{ChangesByKey, ChangesByKeyEq},
+    intern::InternKeys,
+    normalize::NormalizeKeys,
+    scan::{DeltasByKey, ScanByKey},
+};
+
 /// Adds additional methods for `Iterator`s over maps (e.g., `HashMap`,
 /// `BTreeMap`, etc.) and other two-element tuples (like `(K, V)`).
+///
+/// Works with any `Iterator` over items implementing [`KeyValue`], which
+/// includes tuples, two-element arrays, and custom entry types.
 pub trait IterMap<I, K, V>: Sized {
     /// Maps map keys, or the first element of a two-element tuple (like
     /// `(K, V)`), leaving the other element intact and untouched.
     ///
+    /// The closure is only called for items that are yielded, so `count`,
+    /// `nth` and `last` skip it for the items they pass over. Don't rely on it
+    /// for side effects.
+    ///
     /// # Examples
+    ///
     /// ```
     /// # use std::collections::HashMap;
     /// #
@@ -68,6 +177,10 @@ pub trait IterMap<I, K, V>: Sized {
     /// Maps map values (or the second element of a two-element tuple like
     /// `(K, V)`), leaving the other element intact and untouched.
     ///
+    /// The closure is only called for items that are yielded, so `count`,
+    /// `nth` and `last` skip it for the items they pass over. Don't rely on it
+    /// for side effects.
+    ///
     /// # Examples
     ///
     /// ```
@@ -287,41 +400,1503 @@ pub trait IterMap<I, K, V>: Sized {
     /// );
     /// ```
     fn swap(self) -> Swap<Self>;
-}
 
-impl<I, K, V> IterMap<I, K, V> for I
-where
-    I: Iterator<Item = (K, V)>,
-{
-    fn map_keys<Fk, L>(self, key_op: Fk) -> MapKeys<I, Fk>
+    /// Wraps the iterator so it can be formatted like a map, `{k: v, ...}`,
+    /// without collecting it. Useful for logging.
+    ///
+    /// The iterator must be [`Clone`], since it's iterated over each time
+    /// it's formatted. It implements both [`Display`](core::fmt::Display) and
+    /// [`Debug`], using the keys' and values'
+    /// implementations of the same. The separators, quoting, and truncation
+    /// can be configured.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::BTreeMap;
+    /// #
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let map = BTreeMap::from([
+    ///     ("a", "A"),
+    ///     ("b", "B"),
+    ///     ("c", "C"),
+    /// ]);
+    ///
+    /// assert_eq!("{a: A, b: B, c: C}", map.iter().display_map().to_string());
+    /// assert_eq!(
+    ///     r#"{"a": "A", "b": "B", "c": "C"}"#,
+    ///     format!("{:?}", map.iter().display_map()),
+    /// );
+    ///
+    /// let display = map
+    ///     .iter()
+    ///     .display_map()
+    ///     .key_value_separator("=")
+    ///     .quote_values(true)
+    ///     .truncate_after(2);
+    ///
+    /// assert_eq!(r#"{a="A", b="B", ...}"#, display.to_string());
+    /// ```
+    fn display_map(self) -> DisplayMap<I>;
+
+    /// Wraps the iterator so it can be formatted as an aligned, two-column
+    /// table, one item per line, without collecting it.
+    ///
+    /// The iterator must be [`Clone`], since it's iterated over once to
+    /// measure the keys and again to write the items, each time it's
+    /// formatted. Headers, the column separator, and truncation can be
+    /// configured.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::BTreeMap;
+    /// #
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let map = BTreeMap::from([
+    ///     ("cpu", "12%"),
+    ///     ("memory", "1.2 GiB"),
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     "\
+    /// name    usage
+    /// ---------------
+    /// cpu     12%
+    /// memory  1.2 GiB",
+    ///     map.iter().display_table().headers("name", "usage").to_string(),
+    /// );
+    /// ```
+    fn display_table(self) -> DisplayTable<I>;
+
+    /// Sorts the items by map key (or the first element of a two-element
+    /// tuple like `(K, V)`). Useful for getting a deterministic order out of a
+    /// [`HashMap`].
+    ///
+    /// The sort is stable, so items with equal keys keep their relative
+    /// order. All items are buffered when this is called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// #
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let map = HashMap::from([
+    ///     ("c", "C"),
+    ///     ("a", "A"),
+    ///     ("b", "B"),
+    /// ]);
+    ///
+    /// let sorted: Vec<_> = map
+    ///     .into_iter()
+    ///     .sorted_by_key()
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         ("a", "A"),
+    ///         ("b", "B"),
+    ///         ("c", "C"),
+    ///     ],
+    ///     sorted,
+    /// );
+    /// ```
+    ///
+    /// [`HashMap`]: std::collections::HashMap
+    #[cfg(feature = "alloc")]
+    fn sorted_by_key(self) -> Sorted<K, V>
     where
-        Fk: FnMut(K) -> L,
-    {
-        MapKeys::new(self, key_op)
-    }
+        K: Ord;
 
-    fn map_values<Fv, W>(self, value_op: Fv) -> MapValues<I, Fv>
+    /// Like [`IterMap::sorted_by_key`], but the sort is not stable. Items with
+    /// equal keys may be reordered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// #
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let map = HashMap::from([
+    ///     ("c", "C"),
+    ///     ("a", "A"),
+    ///     ("b", "B"),
+    /// ]);
+    ///
+    /// let sorted: Vec<_> = map
+    ///     .into_iter()
+    ///     .sorted_unstable_by_key()
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         ("a", "A"),
+    ///         ("b", "B"),
+    ///         ("c", "C"),
+    ///     ],
+    ///     sorted,
+    /// );
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn sorted_unstable_by_key(self) -> Sorted<K, V>
     where
-        Fv: FnMut(V) -> W,
-    {
-        MapValues::new(self, value_op)
-    }
+        K: Ord;
 
-    fn filter_keys<Fk>(self, key_op: Fk) -> FilterKeys<I, Fk>
+    /// Sorts the items by a value extracted from each map key (or the first
+    /// element of a two-element tuple like `(K, V)`).
+    ///
+    /// The extraction function is called only once per item, like
+    /// [`slice::sort_by_cached_key`]. The sort is stable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// #
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let map = HashMap::from([
+    ///     ("ccc", 3),
+    ///     ("a", 1),
+    ///     ("bb", 2),
+    /// ]);
+    ///
+    /// let sorted: Vec<_> = map
+    ///     .into_iter()
+    ///     .sorted_by_cached_key(|k| k.len())
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         ("a", 1),
+    ///         ("bb", 2),
+    ///         ("ccc", 3),
+    ///     ],
+    ///     sorted,
+    /// );
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn sorted_by_cached_key<Fk, T>(self, key_op: Fk) -> Sorted<K, V>
     where
-        Fk: FnMut(&K) -> bool,
-    {
-        FilterKeys::new(self, key_op)
-    }
+        Fk: FnMut(&K) -> T,
+        T: Ord;
 
-    fn filter_values<Fv>(self, value_op: Fv) -> FilterValues<I, Fv>
+    /// Sorts the items by map value (or the second element of a two-element
+    /// tuple like `(K, V)`).
+    ///
+    /// The sort is stable, so items with equal values keep their relative
+    /// order. All items are buffered when this is called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// #
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let map = HashMap::from([
+    ///     ("a", 3),
+    ///     ("b", 1),
+    ///     ("c", 2),
+    /// ]);
+    ///
+    /// let sorted: Vec<_> = map
+    ///     .into_iter()
+    ///     .sorted_by_value()
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         ("b", 1),
+    ///         ("c", 2),
+    ///         ("a", 3),
+    ///     ],
+    ///     sorted,
+    /// );
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn sorted_by_value(self) -> Sorted<K, V>
     where
-        Fv: FnMut(&V) -> bool,
-    {
-        FilterValues::new(self, value_op)
-    }
+        V: Ord;
 
-    fn swap(self) -> Swap<Self> {
-        Swap::new(self)
+    /// Like [`IterMap::sorted_by_value`], but the sort is not stable. Items
+    /// with equal values may be reordered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// #
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let map = HashMap::from([
+    ///     ("a", 3),
+    ///     ("b", 1),
+    ///     ("c", 2),
+    /// ]);
+    ///
+    /// let sorted: Vec<_> = map
+    ///     .into_iter()
+    ///     .sorted_unstable_by_value()
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         ("b", 1),
+    ///         ("c", 2),
+    ///         ("a", 3),
+    ///     ],
+    ///     sorted,
+    /// );
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn sorted_unstable_by_value(self) -> Sorted<K, V>
+    where
+        V: Ord;
+
+    /// Sorts the items by a value extracted from each map value (or the
+    /// second element of a two-element tuple like `(K, V)`).
+    ///
+    /// The extraction function is called only once per item, like
+    /// [`slice::sort_by_cached_key`]. The sort is stable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// #
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let map = HashMap::from([
+    ///     (3, "ccc"),
+    ///     (1, "a"),
+    ///     (2, "bb"),
+    /// ]);
+    ///
+    /// let sorted: Vec<_> = map
+    ///     .into_iter()
+    ///     .sorted_by_cached_value(|v| v.len())
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         (1, "a"),
+    ///         (2, "bb"),
+    ///         (3, "ccc"),
+    ///     ],
+    ///     sorted,
+    /// );
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn sorted_by_cached_value<Fv, T>(self, value_op: Fv) -> Sorted<K, V>
+    where
+        Fv: FnMut(&V) -> T,
+        T: Ord;
+
+    /// Marks the iterator as yielding items in ascending order by map key (or
+    /// the first element of a two-element tuple like `(K, V)`), without
+    /// checking. See [`SortedByKey`].
+    ///
+    /// Iterators from a [`BTreeMap`] don't need this; use
+    /// [`SortedByKey::from`] instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// // Already sorted, from a database query with `ORDER BY`.
+    /// let rows = vec![(1, "a"), (2, "b"), (3, "c")];
+    ///
+    /// let sorted = rows.into_iter().assume_sorted_by_key().filter_keys(|id| id % 2 == 1);
+    ///
+    /// assert_eq!(vec![(1, "a"), (3, "c")], sorted.collect::<Vec<_>>());
+    /// ```
+    ///
+    /// [`BTreeMap`]: std::collections::BTreeMap
+    fn assume_sorted_by_key(self) -> SortedByKey<I>;
+
+    /// Marks the iterator as yielding items in ascending order by map key (or
+    /// the first element of a two-element tuple like `(K, V)`), checking that
+    /// it does as items are yielded. See [`SortedByKey`].
+    ///
+    /// Equal keys next to each other are allowed.
+    ///
+    /// # Panics
+    ///
+    /// When iterated, panics on the first item with a key less than the key
+    /// before it, with both keys in the message. Use
+    /// [`IterMap::verify_sorted_by_key`] to get an error instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let rows = vec![(1, "a"), (2, "b"), (2, "c")];
+    ///
+    /// let sorted: Vec<_> = rows.clone().into_iter().check_sorted_by_key().collect();
+    /// assert_eq!(rows, sorted);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use itermap::IterMap;
+    ///
+    /// let rows = vec![(2, "b"), (1, "a")];
+    ///
+    /// // Panics on `(1, "a")`
+    /// let sorted: Vec<_> = rows.into_iter().check_sorted_by_key().collect();
+    /// ```
+    fn check_sorted_by_key(self) -> SortedByKey<CheckSorted<I, K>>
+    where
+        K: PartialOrd + Clone + Debug;
+
+    /// Checks that the iterator yields items in ascending order by map key (or
+    /// the first element of a two-element tuple like `(K, V)`), like
+    /// [`IterMap::check_sorted_by_key`], but yields an [`Unsorted`] error for
+    /// each item with a key less than a key before it, instead of panicking.
+    ///
+    /// Equal keys next to each other are allowed. Each key is compared to the
+    /// greatest key before it, so one item out of place is one error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let rows = vec![(1, "a"), (3, "c"), (2, "b"), (4, "d")];
+    ///
+    /// let (sorted, unsorted): (Vec<_>, Vec<_>) = rows
+    ///     .into_iter()
+    ///     .verify_sorted_by_key()
+    ///     .partition(Result::is_ok);
+    ///
+    /// assert_eq!(3, sorted.len());
+    /// assert_eq!(
+    ///     vec!["items are not sorted by key: 2 came after 3"],
+    ///     unsorted
+    ///         .into_iter()
+    ///         .map(|err| err.unwrap_err().to_string())
+    ///         .collect::<Vec<_>>(),
+    /// );
+    /// ```
+    fn verify_sorted_by_key(self) -> VerifySorted<I, K>
+    where
+        K: PartialOrd + Clone;
+
+    /// Yields every key in order, from the first key, filling in the keys
+    /// that are missing. The iterator must be sorted by map key (or the first
+    /// element of a two-element tuple like `(K, V)`), like a time series.
+    ///
+    /// `successor` gives the key after a key, and `fill` gives the value for a
+    /// missing key. `fill` is called with the missing key, the item before it,
+    /// and the item after it, or `None` after the last item. Items with keys
+    /// that aren't one of `successor`'s steps are yielded as they are, and
+    /// stepping continues from them.
+    ///
+    /// By default, this stops after the last item. To stop at a given key
+    /// instead, filling up to it, use [`FillMissingKeys::until`].
+    ///
+    /// Keys and values are cloned to keep the item before each gap.
+    ///
+    /// # Panics
+    ///
+    /// When iterated, panics if `successor` returns a key that isn't greater
+    /// than the key it was given, since stepping would never reach the next
+    /// item. A successor may saturate at the last key, or at the end key
+    /// given to [`FillMissingKeys::until`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let readings = vec![(1, 10), (2, 12), (5, 18)];
+    ///
+    /// // Defaults
+    /// let zeroed: Vec<_> = readings
+    ///     .clone()
+    ///     .into_iter()
+    ///     .fill_missing_keys(|t| t + 1, |_, _, _| 0)
+    ///     .collect();
+    /// assert_eq!(vec![(1, 10), (2, 12), (3, 0), (4, 0), (5, 18)], zeroed);
+    ///
+    /// // Forward-filled, up to an end key
+    /// let held: Vec<_> = readings
+    ///     .clone()
+    ///     .into_iter()
+    ///     .fill_missing_keys(|t| t + 1, |_, (_, last), _| *last)
+    ///     .until(6)
+    ///     .collect();
+    /// assert_eq!(vec![(1, 10), (2, 12), (3, 12), (4, 12), (5, 18), (6, 18)], held);
+    ///
+    /// // Interpolated
+    /// let interpolated: Vec<_> = readings
+    ///     .into_iter()
+    ///     .fill_missing_keys(
+    ///         |t| t + 1,
+    ///         |t, (t0, v0), next| match next {
+    ///             Some((t1, v1)) => v0 + (v1 - v0) * (t - t0) / (t1 - t0),
+    ///             None => *v0,
+    ///         },
+    ///     )
+    ///     .collect();
+    /// assert_eq!(vec![(1, 10), (2, 12), (3, 14), (4, 16), (5, 18)], interpolated);
+    /// ```
+    fn fill_missing_keys<Fs, Ff>(self, successor: Fs, fill: Ff) -> FillMissingKeys<I, K, V, Fs, Ff>
+    where
+        K: PartialOrd + Clone,
+        V: Clone,
+        Fs: FnMut(&K) -> K,
+        Ff: FnMut(&K, (&K, &V), Option<(&K, &V)>) -> V;
+
+    /// Flattens a map of maps, yielding `((K, K2), V2)` for each item of each
+    /// value. Values can be anything that iterates over key-value pairs, and
+    /// values with no items are skipped.
+    ///
+    /// For three or more levels, flatten the inner maps first with
+    /// [`IterMap::map_values`], which gives keys like `(K, (K2, K3))` that
+    /// [`IterMap::unflatten_into`] can turn back into nested maps.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    ///
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let quotas = BTreeMap::from([
+    ///     ("acme", BTreeMap::from([("cpu", 4), ("disk", 100)])),
+    ///     ("initech", BTreeMap::from([("cpu", 2)])),
+    /// ]);
+    ///
+    /// let rows: Vec<_> = quotas.into_iter().flatten_nested().collect();
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         (("acme", "cpu"), 4),
+    ///         (("acme", "disk"), 100),
+    ///         (("initech", "cpu"), 2),
+    ///     ],
+    ///     rows,
+    /// );
+    /// ```
+    fn flatten_nested(self) -> FlattenNested<I, K, V::IntoIter>
+    where
+        V: IntoIterator,
+        V::Item: KeyValue,
+        K: Clone;
+
+    /// Collects items with composite keys like `(K, K2)` into nested maps, the
+    /// reverse of [`IterMap::flatten_nested`]. See [`Unflatten`] for the map
+    /// types supported and how keys are split across levels.
+    ///
+    /// Later values replace earlier ones with the same composite key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    ///
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let rows = vec![
+    ///     (("acme", ("eu", "cpu")), 4),
+    ///     (("acme", ("us", "cpu")), 8),
+    ///     (("initech", ("eu", "disk")), 50),
+    /// ];
+    ///
+    /// let quotas: BTreeMap<_, BTreeMap<_, BTreeMap<_, _>>> = rows.into_iter().unflatten_into();
+    ///
+    /// assert_eq!(8, quotas["acme"]["us"]["cpu"]);
+    /// assert_eq!(vec!["eu", "us"], quotas["acme"].keys().copied().collect::<Vec<_>>());
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn unflatten_into<M>(self) -> M
+    where
+        M: Unflatten<K, V> + Default;
+
+    /// Transposes a map of maps, so rows become columns: a value at `[K][K2]`
+    /// ends up at `[K2][K]`. The output can be any nested map type supported
+    /// by [`Unflatten`], such as
+    /// a `HashMap<K2, HashMap<K, V2>>` or a `BTreeMap<K2, BTreeMap<K, V2>>`.
+    ///
+    /// Rows with no items don't appear in the output. To keep them, or to
+    /// get a table with every cell filled, see
+    /// [`IterMap::transpose_dense`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    ///
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let sales = BTreeMap::from([
+    ///     ("north", BTreeMap::from([("jan", 10), ("feb", 12)])),
+    ///     ("south", BTreeMap::from([("jan", 7)])),
+    /// ]);
+    ///
+    /// let by_month: BTreeMap<_, BTreeMap<_, _>> = sales.into_iter().transpose_nested();
+    ///
+    /// assert_eq!(BTreeMap::from([("north", 10), ("south", 7)]), by_month["jan"]);
+    /// assert_eq!(BTreeMap::from([("north", 12)]), by_month["feb"]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn transpose_nested<M, K2, V2>(self) -> M
+    where
+        V: IntoIterator,
+        V::Item: KeyValue<Key = K2, Value = V2>,
+        K: Clone,
+        M: Unflatten<(K2, K), V2> + Default;
+
+    /// Transposes a map of maps into a [`DenseTable`], with a row for each
+    /// key of the values, and a column for each key of the map. Cells with no
+    /// value are set to `fill`. Rows and columns are sorted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    ///
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let sales = BTreeMap::from([
+    ///     ("north", BTreeMap::from([("feb", 12), ("jan", 10)])),
+    ///     ("south", BTreeMap::from([("jan", 7)])),
+    /// ]);
+    ///
+    /// let table = sales.into_iter().transpose_dense(0);
+    ///
+    /// assert_eq!(vec!["feb", "jan"], table.rows);
+    /// assert_eq!(vec!["north", "south"], table.columns);
+    /// assert_eq!(vec![vec![12, 0], vec![10, 7]], table.cells);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn transpose_dense<K2, V2>(self, fill: V2) -> DenseTable<K2, K, V2>
+    where
+        V: IntoIterator,
+        V::Item: KeyValue<Key = K2, Value = V2>,
+        K: Ord + Clone,
+        K2: Ord,
+        V2: Clone;
+
+    /// Replaces map keys (or the first element of a two-element tuple like
+    /// `(K, V)`) with dense integer ids, yielding `(Id, V)`. Ids are assigned
+    /// in the order keys are first seen, starting at `0`.
+    ///
+    /// The [`Interner`] that maps ids back to keys (and keys to ids) can be
+    /// retrieved from the adaptor with [`InternKeys::interner`] or
+    /// [`InternKeys::into_interner`]. To keep ids stable across batches, see
+    /// [`IterMap::intern_keys_with`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more distinct keys than `Id` can represent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let rows = vec![
+    ///     (String::from("cpu"), 0.5),
+    ///     (String::from("mem"), 0.25),
+    ///     (String::from("cpu"), 0.75),
+    /// ];
+    ///
+    /// let mut iter = rows.into_iter().intern_keys::<u32>();
+    /// let interned: Vec<_> = iter.by_ref().collect();
+    ///
+    /// assert_eq!(vec![(0, 0.5), (1, 0.25), (0, 0.75)], interned);
+    ///
+    /// let interner = iter.into_interner();
+    /// assert_eq!(&[String::from("cpu"), String::from("mem")], interner.keys());
+    /// ```
+    ///
+    /// [`InternKeys::interner`]: crate::InternKeys::interner
+    /// [`InternKeys::into_interner`]: crate::InternKeys::into_interner
+    #[cfg(feature = "std")]
+    fn intern_keys<Id>(self) -> InternKeys<I, K, Id>
+    where
+        K: Eq + Hash + Clone,
+        Id: InternId;
+
+    /// Like [`IterMap::intern_keys`], but starts with an existing
+    /// [`Interner`]. Keys it already holds keep their ids, and new keys are
+    /// assigned the next ids.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more distinct keys than `Id` can represent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::{Interner, IterMap};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let mut interner = Interner::<&str, u32>::new();
+    ///
+    /// for batch in [vec![("a", 1), ("b", 2)], vec![("b", 3), ("c", 4)]] {
+    ///     let mut iter = batch.into_iter().intern_keys_with(interner);
+    ///     let interned: Vec<_> = iter.by_ref().collect();
+    ///     println!("{interned:?}");
+    ///
+    ///     interner = iter.into_interner();
+    /// }
+    ///
+    /// assert_eq!(&["a", "b", "c"], interner.keys());
+    /// ```
+    #[cfg(feature = "std")]
+    fn intern_keys_with<Id>(self, interner: Interner<K, Id>) -> InternKeys<I, K, Id>
+    where
+        K: Eq + Hash + Clone,
+        Id: InternId;
+
+    /// Keeps running state for each map key (or the first element of a
+    /// two-element tuple like `(K, V)`), yielding `(K, W)` for each item, like
+    /// [`Iterator::scan`] per key.
+    ///
+    /// Each key's state starts as a clone of `init`, and `f` is called with
+    /// it and the value. The states are kept in a `HashMap`, which can be
+    /// retrieved with [`ScanByKey::states`] or [`ScanByKey::into_states`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let transactions = vec![("alice", 100), ("bob", 20), ("alice", -30)];
+    ///
+    /// let balances: Vec<_> = transactions
+    ///     .into_iter()
+    ///     .scan_by_key(0, |balance, amount| {
+    ///         *balance += amount;
+    ///         *balance
+    ///     })
+    ///     .collect();
+    ///
+    /// assert_eq!(vec![("alice", 100), ("bob", 20), ("alice", 70)], balances);
+    /// ```
+    ///
+    /// [`ScanByKey::states`]: crate::ScanByKey::states
+    /// [`ScanByKey::into_states`]: crate::ScanByKey::into_states
+    #[cfg(feature = "std")]
+    fn scan_by_key<S, F, W>(self, init: S, f: F) -> ScanByKey<I, K, S, F>
+    where
+        K: Eq + Hash + Clone,
+        S: Clone,
+        F: FnMut(&mut S, V) -> W;
+
+    /// Yields the difference between each value and the previous value for
+    /// the same map key (or the first element of a two-element tuple like
+    /// `(K, V)`), or `None` the first time a key is seen.
+    ///
+    /// The last value for each key is kept in a `HashMap`; see
+    /// [`IterMap::scan_by_key`].
+    ///
+    /// # Overflow Behavior
+    ///
+    /// Values are subtracted with `-`, so a delta that doesn't fit in the
+    /// type, like a decreasing unsigned value, panics in debug builds and
+    /// wraps in release builds. For unsigned values, or when overflow is
+    /// possible, use [`IterMap::scan_by_key`] with a subtraction that handles
+    /// it, like `checked_sub` or `wrapping_sub`:
+    ///
+    /// ```
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let readings = vec![("meter", 10_u32), ("meter", 15), ("meter", 12)];
+    ///
+    /// let deltas: Vec<_> = readings
+    ///     .into_iter()
+    ///     .scan_by_key(None, |previous: &mut Option<u32>, value| {
+    ///         previous.replace(value).map(|previous| value.checked_sub(previous))
+    ///     })
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     vec![("meter", None), ("meter", Some(Some(5))), ("meter", Some(None))],
+    ///     deltas,
+    /// );
+    /// ```
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let readings = vec![("meter", 10), ("gauge", 3), ("meter", 15), ("gauge", 1)];
+    ///
+    /// let deltas: Vec<_> = readings.into_iter().deltas_by_key().collect();
+    ///
+    /// assert_eq!(
+    ///     vec![("meter", None), ("gauge", None), ("meter", Some(5)), ("gauge", Some(-2))],
+    ///     deltas,
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    fn deltas_by_key(self) -> DeltasByKey<I, K, V>
+    where
+        K: Eq + Hash + Clone,
+        V: Sub + Clone;
+
+    /// Only yields items whose value differs from the last value yielded for
+    /// the same map key (or the first element of a two-element tuple like
+    /// `(K, V)`). The first item for each key is always yielded.
+    ///
+    /// The last value for each key is kept in a `HashMap`. To bound how many
+    /// keys are remembered, see [`ChangesByKey::max_keys`]. To compare values
+    /// with something other than [`PartialEq`], see
+    /// [`IterMap::changes_by_key_by`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let telemetry = vec![("temp", 20), ("fan", 1), ("temp", 20), ("fan", 2), ("temp", 20)];
+    ///
+    /// let changes: Vec<_> = telemetry.into_iter().changes_by_key().collect();
+    ///
+    /// assert_eq!(vec![("temp", 20), ("fan", 1), ("fan", 2)], changes);
+    /// ```
+    ///
+    /// [`ChangesByKey::max_keys`]: crate::ChangesByKey::max_keys
+    #[cfg(feature = "std")]
+    fn changes_by_key(self) -> ChangesByKeyEq<I, K, V>
+    where
+        K: Eq + Hash + Clone,
+        V: PartialEq + Clone;
+
+    /// Like [`IterMap::changes_by_key`], but values are compared with `eq`,
+    /// which returns `true` if the value hasn't changed from the last value
+    /// yielded for the key.
+    ///
+    /// Since values are compared to the last value yielded, rather than the
+    /// last value seen, slow drift is still yielded once it adds up.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let readings = vec![("temp", 20.0), ("temp", 20.05), ("temp", 20.1), ("temp", 21.0)];
+    ///
+    /// let changes: Vec<_> = readings
+    ///     .into_iter()
+    ///     .changes_by_key_by(|last: &f64, value| (last - value).abs() <= 0.5)
+    ///     .collect();
+    ///
+    /// assert_eq!(vec![("temp", 20.0), ("temp", 21.0)], changes);
+    /// ```
+    #[cfg(feature = "std")]
+    fn changes_by_key_by<F>(self, eq: F) -> ChangesByKey<I, K, V, F>
+    where
+        K: Eq + Hash + Clone,
+        V: Clone,
+        F: FnMut(&V, &V) -> bool;
+
+    /// Maps map keys (or the first element of a two-element tuple like
+    /// `(K, V)`) like [`IterMap::map_keys`], but detects when two original
+    /// keys map to the same normalized key instead of silently losing data
+    /// when collected into a map.
+    ///
+    /// Call one of the methods on the result to choose how collisions are
+    /// handled:
+    ///
+    /// * [`keep_first`](NormalizeKeys::keep_first)
+    /// * [`keep_last`](NormalizeKeys::keep_last)
+    /// * [`combine`](NormalizeKeys::combine)
+    /// * [`require_unique`](NormalizeKeys::require_unique), which returns a
+    ///   [`KeyCollision`] error with both original keys.
+    ///
+    /// The [`Normalized`] result reports the collisions that were resolved.
+    /// All items are buffered when one of those methods is called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// #
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let headers = vec![
+    ///     ("Accept", "text/html"),
+    ///     ("Cache-Control", "no-cache"),
+    ///     ("accept", "application/json"),
+    /// ];
+    ///
+    /// let normalized = headers
+    ///     .clone()
+    ///     .into_iter()
+    ///     .normalize_keys(|k| k.to_lowercase())
+    ///     .combine(|a, b| if a.len() > b.len() { a } else { b });
+    ///
+    /// let collision = &normalized.collisions()[0];
+    /// assert_eq!(("Accept", "accept"), (collision.first, collision.second));
+    ///
+    /// let map: HashMap<_, _> = normalized.into_iter().collect();
+    /// assert_eq!(Some(&"application/json"), map.get("accept"));
+    ///
+    /// let err = headers
+    ///     .into_iter()
+    ///     .normalize_keys(|k| k.to_lowercase())
+    ///     .require_unique()
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     r#"keys "Accept" and "accept" both normalize to "accept""#,
+    ///     err.to_string(),
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    fn normalize_keys<Fk, L>(self, key_op: Fk) -> NormalizeKeys<I, Fk>
+    where
+        Fk: FnMut(&K) -> L,
+        L: Eq + Hash + Clone;
+
+    /// Splits items whose map values (or the second element of a two-element
+    /// tuple like `(K, V)`) are [`Result`]s into two collections: one of
+    /// `(K, T)` for the `Ok` values, and one of `(K, E)` for the `Err` values.
+    ///
+    /// Unlike collecting into a `Result`, this doesn't stop at the first
+    /// error. Pairs well with [`IterMap::map_values`] producing `Result`s.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// #
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let map = HashMap::from([
+    ///     ("a", "1"),
+    ///     ("b", "two"),
+    ///     ("c", "3"),
+    /// ]);
+    ///
+    /// let (oks, errs): (HashMap<_, _>, HashMap<_, _>) = map
+    ///     .into_iter()
+    ///     .map_values(str::parse::<u32>)
+    ///     .partition_result_values();
+    ///
+    /// assert_eq!(HashMap::from([("a", 1), ("c", 3)]), oks);
+    /// assert_eq!(vec![&"b"], errs.keys().collect::<Vec<_>>());
+    /// ```
+    fn partition_result_values<A, B>(self) -> (A, B)
+    where
+        V: ResultValue,
+        A: Default + Extend<(K, V::Ok)>,
+        B: Default + Extend<(K, V::Err)>;
+
+    /// Collects items whose map values (or the second element of a two-element
+    /// tuple like `(K, V)`) are [`Result`]s. If they're all `Ok`, returns the
+    /// collection of `(K, T)`. Otherwise returns the collection of every
+    /// `(K, E)`, not just the first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// #
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let valid = vec![("a", "1"), ("b", "2")];
+    ///
+    /// let all: Result<HashMap<_, _>, Vec<_>> = valid
+    ///     .into_iter()
+    ///     .map_values(str::parse::<u32>)
+    ///     .collect_all_or_errors();
+    ///
+    /// assert_eq!(Ok(HashMap::from([("a", 1), ("b", 2)])), all);
+    ///
+    /// let invalid = vec![("a", "one"), ("b", "2"), ("c", "three")];
+    ///
+    /// let all: Result<HashMap<_, _>, Vec<_>> = invalid
+    ///     .into_iter()
+    ///     .map_values(str::parse::<u32>)
+    ///     .collect_all_or_errors();
+    ///
+    /// let errs = all.unwrap_err();
+    /// assert_eq!(vec!["a", "c"], errs.iter().map(|(k, _)| *k).collect::<Vec<_>>());
+    /// ```
+    fn collect_all_or_errors<A, B>(self) -> Result<A, B>
+    where
+        V: ResultValue,
+        A: Default + Extend<(K, V::Ok)>,
+        B: Default + Extend<(K, V::Err)>;
+
+    /// Returns the value of the first item whose map key (or the first element
+    /// of a two-element tuple like `(K, V)`) is equal to `key`.
+    ///
+    /// Like [`Iterator::find`], this is short-circuiting; it stops as soon as
+    /// a matching key is found. Useful for treating a `Vec<(K, V)>` as an
+    /// association list without converting it to a map first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let items = vec![
+    ///     (String::from("a"), "A"),
+    ///     (String::from("b"), "B"),
+    ///     (String::from("c"), "C"),
+    /// ];
+    ///
+    /// assert_eq!(Some("B"), items.clone().into_iter().get("b"));
+    /// assert_eq!(None, items.into_iter().get("d"));
+    /// ```
+    fn get<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq;
+
+    /// Returns `true` if any item has a map key (or the first element of a
+    /// two-element tuple like `(K, V)`) equal to `key`.
+    ///
+    /// Like [`Iterator::any`], this is short-circuiting.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IterMap;
+    ///
+    /// let items = vec![
+    ///     ("a", "A"),
+    ///     ("b", "B"),
+    ///     ("c", "C"),
+    /// ];
+    ///
+    /// assert!(items.iter().copied().contains_key("b"));
+    /// assert!(!items.iter().copied().contains_key("d"));
+    /// ```
+    fn contains_key<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq;
+
+    /// Returns the index of the first item whose map key (or the first element
+    /// of a two-element tuple like `(K, V)`) is equal to `key`.
+    ///
+    /// Like [`Iterator::position`], this is short-circuiting.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let items = vec![
+    ///     ("a", "A"),
+    ///     ("b", "B"),
+    ///     ("c", "C"),
+    /// ];
+    ///
+    /// assert_eq!(Some(1), items.iter().copied().position_of_key("b"));
+    /// assert_eq!(None, items.iter().copied().position_of_key("d"));
+    /// ```
+    fn position_of_key<Q>(&mut self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq;
+
+    /// Returns the first item whose map key (or the first element of a
+    /// two-element tuple like `(K, V)`) satisfies the predicate.
+    ///
+    /// Like [`Iterator::find`], this is short-circuiting.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let items = vec![
+    ///     ("a", 1),
+    ///     ("bb", 2),
+    ///     ("cc", 3),
+    /// ];
+    ///
+    /// assert_eq!(
+    ///     Some(("bb", 2)),
+    ///     items.into_iter().find_by_key(|k| k.len() == 2),
+    /// );
+    /// ```
+    fn find_by_key<Fk>(&mut self, key_op: Fk) -> Option<(K, V)>
+    where
+        Fk: FnMut(&K) -> bool;
+
+    /// Returns the first item whose map value (or the second element of a
+    /// two-element tuple like `(K, V)`) satisfies the predicate.
+    ///
+    /// Like [`Iterator::find`], this is short-circuiting.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IterMap;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let items = vec![
+    ///     ("a", 1),
+    ///     ("b", 2),
+    ///     ("c", 3),
+    /// ];
+    ///
+    /// assert_eq!(
+    ///     Some(("b", 2)),
+    ///     items.into_iter().find_by_value(|v| v % 2 == 0),
+    /// );
+    /// ```
+    fn find_by_value<Fv>(&mut self, value_op: Fv) -> Option<(K, V)>
+    where
+        Fv: FnMut(&V) -> bool;
+
+    /// Returns `true` if the map key (or the first element of a two-element
+    /// tuple like `(K, V)`) of any item satisfies the predicate.
+    ///
+    /// Like [`Iterator::any`], this is short-circuiting.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IterMap;
+    ///
+    /// let items = vec![
+    ///     ("a", 1),
+    ///     ("b", 2),
+    ///     ("c", 3),
+    /// ];
+    ///
+    /// assert!(items.iter().copied().any_key(|k| k == &"b"));
+    /// assert!(!items.iter().copied().any_key(|k| k == &"d"));
+    /// ```
+    fn any_key<Fk>(&mut self, key_op: Fk) -> bool
+    where
+        Fk: FnMut(&K) -> bool;
+
+    /// Returns `true` if the map value (or the second element of a two-element
+    /// tuple like `(K, V)`) of any item satisfies the predicate.
+    ///
+    /// Like [`Iterator::any`], this is short-circuiting.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IterMap;
+    ///
+    /// let items = vec![
+    ///     ("a", 1),
+    ///     ("b", 2),
+    ///     ("c", 3),
+    /// ];
+    ///
+    /// assert!(items.iter().copied().any_value(|v| v > &2));
+    /// assert!(!items.iter().copied().any_value(|v| v > &3));
+    /// ```
+    fn any_value<Fv>(&mut self, value_op: Fv) -> bool
+    where
+        Fv: FnMut(&V) -> bool;
+
+    /// Returns `true` if the map keys (or the first elements of two-element
+    /// tuples like `(K, V)`) of all items satisfy the predicate.
+    ///
+    /// Like [`Iterator::all`], this is short-circuiting.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IterMap;
+    ///
+    /// let items = vec![
+    ///     ("a", 1),
+    ///     ("b", 2),
+    ///     ("c", 3),
+    /// ];
+    ///
+    /// assert!(items.iter().copied().all_keys(|k| k.len() == 1));
+    /// assert!(!items.iter().copied().all_keys(|k| k == &"a"));
+    /// ```
+    fn all_keys<Fk>(&mut self, key_op: Fk) -> bool
+    where
+        Fk: FnMut(&K) -> bool;
+
+    /// Returns `true` if the map values (or the second elements of two-element
+    /// tuples like `(K, V)`) of all items satisfy the predicate.
+    ///
+    /// Like [`Iterator::all`], this is short-circuiting.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IterMap;
+    ///
+    /// let items = vec![
+    ///     ("a", 1),
+    ///     ("b", 2),
+    ///     ("c", 3),
+    /// ];
+    ///
+    /// assert!(items.iter().copied().all_values(|v| v > &0));
+    /// assert!(!items.iter().copied().all_values(|v| v > &1));
+    /// ```
+    fn all_values<Fv>(&mut self, value_op: Fv) -> bool
+    where
+        Fv: FnMut(&V) -> bool;
+}
+
+impl<I, K, V> IterMap<I, K, V> for I
+where
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+{
+    fn map_keys<Fk, L>(self, key_op: Fk) -> MapKeys<I, Fk>
+    where
+        Fk: FnMut(K) -> L,
+    {
+        MapKeys::new(self, key_op)
+    }
+
+    fn map_values<Fv, W>(self, value_op: Fv) -> MapValues<I, Fv>
+    where
+        Fv: FnMut(V) -> W,
+    {
+        MapValues::new(self, value_op)
+    }
+
+    fn filter_keys<Fk>(self, key_op: Fk) -> FilterKeys<I, Fk>
+    where
+        Fk: FnMut(&K) -> bool,
+    {
+        FilterKeys::new(self, key_op)
+    }
+
+    fn filter_values<Fv>(self, value_op: Fv) -> FilterValues<I, Fv>
+    where
+        Fv: FnMut(&V) -> bool,
+    {
+        FilterValues::new(self, value_op)
+    }
+
+    fn swap(self) -> Swap<Self> {
+        Swap::new(self)
+    }
+
+    fn display_map(self) -> DisplayMap<I> {
+        DisplayMap::new(self)
+    }
+
+    fn display_table(self) -> DisplayTable<I> {
+        DisplayTable::new(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn sorted_by_key(self) -> Sorted<K, V>
+    where
+        K: Ord,
+    {
+        Sorted::new(self, |items| items.sort_by(|a, b| a.0.cmp(&b.0)))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn sorted_unstable_by_key(self) -> Sorted<K, V>
+    where
+        K: Ord,
+    {
+        Sorted::new(self, |items| items.sort_unstable_by(|a, b| a.0.cmp(&b.0)))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn sorted_by_cached_key<Fk, T>(self, mut key_op: Fk) -> Sorted<K, V>
+    where
+        Fk: FnMut(&K) -> T,
+        T: Ord,
+    {
+        Sorted::new(self, |items| items.sort_by_cached_key(|(k, _)| key_op(k)))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn sorted_by_value(self) -> Sorted<K, V>
+    where
+        V: Ord,
+    {
+        Sorted::new(self, |items| items.sort_by(|a, b| a.1.cmp(&b.1)))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn sorted_unstable_by_value(self) -> Sorted<K, V>
+    where
+        V: Ord,
+    {
+        Sorted::new(self, |items| items.sort_unstable_by(|a, b| a.1.cmp(&b.1)))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn sorted_by_cached_value<Fv, T>(self, mut value_op: Fv) -> Sorted<K, V>
+    where
+        Fv: FnMut(&V) -> T,
+        T: Ord,
+    {
+        Sorted::new(self, |items| items.sort_by_cached_key(|(_, v)| value_op(v)))
+    }
+
+    fn assume_sorted_by_key(self) -> SortedByKey<I> {
+        SortedByKey::new(self)
+    }
+
+    fn check_sorted_by_key(self) -> SortedByKey<CheckSorted<I, K>>
+    where
+        K: PartialOrd + Clone + Debug,
+    {
+        SortedByKey::new(CheckSorted::new(self))
+    }
+
+    fn verify_sorted_by_key(self) -> VerifySorted<I, K>
+    where
+        K: PartialOrd + Clone,
+    {
+        VerifySorted::new(self)
+    }
+
+    fn fill_missing_keys<Fs, Ff>(self, successor: Fs, fill: Ff) -> FillMissingKeys<I, K, V, Fs, Ff>
+    where
+        K: PartialOrd + Clone,
+        V: Clone,
+        Fs: FnMut(&K) -> K,
+        Ff: FnMut(&K, (&K, &V), Option<(&K, &V)>) -> V,
+    {
+        FillMissingKeys::new(self, successor, fill)
+    }
+
+    fn flatten_nested(self) -> FlattenNested<I, K, V::IntoIter>
+    where
+        V: IntoIterator,
+        V::Item: KeyValue,
+        K: Clone,
+    {
+        FlattenNested::new(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn unflatten_into<M>(self) -> M
+    where
+        M: Unflatten<K, V> + Default,
+    {
+        let mut map = M::default();
+        for (key, value) in self.map(KeyValue::into_parts) {
+            map.insert_nested(key, value);
+        }
+        map
+    }
+
+    #[cfg(feature = "alloc")]
+    fn transpose_nested<M, K2, V2>(self) -> M
+    where
+        V: IntoIterator,
+        V::Item: KeyValue<Key = K2, Value = V2>,
+        K: Clone,
+        M: Unflatten<(K2, K), V2> + Default,
+    {
+        self.flatten_nested()
+            .map_keys(|(key, key2)| (key2, key))
+            .unflatten_into()
+    }
+
+    #[cfg(feature = "alloc")]
+    fn transpose_dense<K2, V2>(self, fill: V2) -> DenseTable<K2, K, V2>
+    where
+        V: IntoIterator,
+        V::Item: KeyValue<Key = K2, Value = V2>,
+        K: Ord + Clone,
+        K2: Ord,
+        V2: Clone,
+    {
+        DenseTable::transposed(self, fill)
+    }
+
+    #[cfg(feature = "std")]
+    fn intern_keys<Id>(self) -> InternKeys<I, K, Id>
+    where
+        K: Eq + Hash + Clone,
+        Id: InternId,
+    {
+        InternKeys::new(self, Interner::new())
+    }
+
+    #[cfg(feature = "std")]
+    fn intern_keys_with<Id>(self, interner: Interner<K, Id>) -> InternKeys<I, K, Id>
+    where
+        K: Eq + Hash + Clone,
+        Id: InternId,
+    {
+        InternKeys::new(self, interner)
+    }
+
+    #[cfg(feature = "std")]
+    fn scan_by_key<S, F, W>(self, init: S, f: F) -> ScanByKey<I, K, S, F>
+    where
+        K: Eq + Hash + Clone,
+        S: Clone,
+        F: FnMut(&mut S, V) -> W,
+    {
+        ScanByKey::new(self, init, f)
+    }
+
+    #[cfg(feature = "std")]
+    fn deltas_by_key(self) -> DeltasByKey<I, K, V>
+    where
+        K: Eq + Hash + Clone,
+        V: Sub + Clone,
+    {
+        ScanByKey::new(self, None, scan::delta)
+    }
+
+    #[cfg(feature = "std")]
+    fn changes_by_key(self) -> ChangesByKeyEq<I, K, V>
+    where
+        K: Eq + Hash + Clone,
+        V: PartialEq + Clone,
+    {
+        ChangesByKey::new(self, V::eq)
+    }
+
+    #[cfg(feature = "std")]
+    fn changes_by_key_by<F>(self, eq: F) -> ChangesByKey<I, K, V, F>
+    where
+        K: Eq + Hash + Clone,
+        V: Clone,
+        F: FnMut(&V, &V) -> bool,
+    {
+        ChangesByKey::new(self, eq)
+    }
+
+    #[cfg(feature = "std")]
+    fn normalize_keys<Fk, L>(self, key_op: Fk) -> NormalizeKeys<I, Fk>
+    where
+        Fk: FnMut(&K) -> L,
+        L: Eq + Hash + Clone,
+    {
+        NormalizeKeys::new(self, key_op)
+    }
+
+    fn partition_result_values<A, B>(self) -> (A, B)
+    where
+        V: ResultValue,
+        A: Default + Extend<(K, V::Ok)>,
+        B: Default + Extend<(K, V::Err)>,
+    {
+        result::partition(self)
+    }
+
+    fn collect_all_or_errors<A, B>(self) -> Result<A, B>
+    where
+        V: ResultValue,
+        A: Default + Extend<(K, V::Ok)>,
+        B: Default + Extend<(K, V::Err)>,
+    {
+        result::collect_all_or_errors(self)
+    }
+
+    fn get<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq,
+    {
+        self.find(|item| item.with_key(|k| k.borrow() == key))
+            .map(|item| item.into_parts().1)
+    }
+
+    fn contains_key<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq,
+    {
+        self.any(|item| item.with_key(|k| k.borrow() == key))
+    }
+
+    fn position_of_key<Q>(&mut self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq,
+    {
+        self.position(|item| item.with_key(|k| k.borrow() == key))
+    }
+
+    fn find_by_key<Fk>(&mut self, mut key_op: Fk) -> Option<(K, V)>
+    where
+        Fk: FnMut(&K) -> bool,
+    {
+        self.find(|item| item.with_key(&mut key_op))
+            .map(KeyValue::into_parts)
+    }
+
+    fn find_by_value<Fv>(&mut self, mut value_op: Fv) -> Option<(K, V)>
+    where
+        Fv: FnMut(&V) -> bool,
+    {
+        self.find(|item| item.with_value(&mut value_op))
+            .map(KeyValue::into_parts)
+    }
+
+    fn any_key<Fk>(&mut self, mut key_op: Fk) -> bool
+    where
+        Fk: FnMut(&K) -> bool,
+    {
+        self.any(|item| item.with_key(&mut key_op))
+    }
+
+    fn any_value<Fv>(&mut self, mut value_op: Fv) -> bool
+    where
+        Fv: FnMut(&V) -> bool,
+    {
+        self.any(|item| item.with_value(&mut value_op))
+    }
+
+    fn all_keys<Fk>(&mut self, mut key_op: Fk) -> bool
+    where
+        Fk: FnMut(&K) -> bool,
+    {
+        self.all(|item| item.with_key(&mut key_op))
+    }
+
+    fn all_values<Fv>(&mut self, mut value_op: Fv) -> bool
+    where
+        Fv: FnMut(&V) -> bool,
+    {
+        self.all(|item| item.with_value(&mut value_op))
     }
 }
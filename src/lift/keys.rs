@@ -0,0 +1,129 @@
+use core::{
+    fmt,
+    iter::{DoubleEndedIterator, FusedIterator, Iterator},
+};
+
+/// Pairs each item with a key computed from it, yielding `(K, T)`.
+///
+/// For use, see the documentation on [`IntoPairs::key_by`].
+///
+/// [`IntoPairs::key_by`]: crate::IntoPairs::key_by
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct KeyBy<I, P> {
+    iter: I,
+    predicate: P,
+}
+
+impl<I, P> KeyBy<I, P> {
+    pub(crate) fn new<K>(iter: I, predicate: P) -> Self
+    where
+        I: Iterator,
+        P: FnMut(&I::Item) -> K,
+    {
+        Self { iter, predicate }
+    }
+
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items in this adaptor.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the adaptor, returning the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I, P> fmt::Debug for KeyBy<I, P>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyBy")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I, P, K> Iterator for KeyBy<I, P>
+where
+    I: Iterator,
+    P: FnMut(&I::Item) -> K,
+{
+    type Item = (K, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|t| ((self.predicate)(&t), t))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut predicate = self.predicate;
+        self.iter
+            .fold(init, move |acc, t| f(acc, (predicate(&t), t)))
+    }
+
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n).map(|t| ((self.predicate)(&t), t))
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        let mut predicate = self.predicate;
+        self.iter.last().map(|t| (predicate(&t), t))
+    }
+}
+
+impl<I, P, K> DoubleEndedIterator for KeyBy<I, P>
+where
+    I: DoubleEndedIterator,
+    P: FnMut(&I::Item) -> K,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|t| ((self.predicate)(&t), t))
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut predicate = self.predicate;
+        self.iter
+            .rfold(init, move |acc, t| f(acc, (predicate(&t), t)))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth_back(n).map(|t| ((self.predicate)(&t), t))
+    }
+}
+
+impl<I, P, K> FusedIterator for KeyBy<I, P>
+where
+    I: FusedIterator,
+    P: FnMut(&I::Item) -> K,
+{
+}
+
+impl<I, P, K> ExactSizeIterator for KeyBy<I, P>
+where
+    I: ExactSizeIterator,
+    P: FnMut(&I::Item) -> K,
+{
+}
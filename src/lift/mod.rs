@@ -0,0 +1,293 @@
+mod keys;
+mod pairs;
+mod values;
+
+use core::iter::{Iterator, Zip};
+
+pub use keys::KeyBy;
+pub use pairs::{Pairs, PairsMut};
+pub use values::ValueBy;
+
+/// Adds methods for turning `Iterator`s over single items into `Iterator`s
+/// over two-element tuples (like `(K, V)`), so they can be used with
+/// [`IterMap`].
+///
+/// [`IterMap`]: crate::IterMap
+pub trait IntoPairs: Iterator + Sized {
+    /// Pairs each item with a key computed from it, yielding `(K, T)`.
+    ///
+    /// As with [`IterMap::map_keys`](crate::IterMap::map_keys), the closure is
+    /// only called for items that are yielded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// #
+    /// use itermap::{IntoPairs, IterMap};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let words = vec!["apple", "banana", "cherry"];
+    ///
+    /// let map: HashMap<char, String> = words
+    ///     .into_iter()
+    ///     .key_by(|w| w.chars().next().unwrap())
+    ///     .map_values(String::from)
+    ///     .collect();
+    ///
+    /// assert_eq!(Some("banana"), map.get(&'b').map(String::as_str));
+    /// ```
+    fn key_by<F, K>(self, f: F) -> KeyBy<Self, F>
+    where
+        F: FnMut(&Self::Item) -> K,
+    {
+        KeyBy::new(self, f)
+    }
+
+    /// Pairs each item with a value computed from it, yielding `(T, V)`.
+    ///
+    /// As with [`IterMap::map_keys`](crate::IterMap::map_keys), the closure is
+    /// only called for items that are yielded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// #
+    /// use itermap::{IntoPairs, IterMap};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let words = vec!["a", "bb", "ccc"];
+    ///
+    /// let map: HashMap<&str, usize> = words
+    ///     .into_iter()
+    ///     .value_by(|w| w.len())
+    ///     .filter_values(|len| len > &1)
+    ///     .collect();
+    ///
+    /// assert_eq!(HashMap::from([("bb", 2), ("ccc", 3)]), map);
+    /// ```
+    fn value_by<F, V>(self, f: F) -> ValueBy<Self, F>
+    where
+        F: FnMut(&Self::Item) -> V,
+    {
+        ValueBy::new(self, f)
+    }
+
+    /// Pairs each item, as the value, with the next item from `keys`,
+    /// yielding `(K, T)`. Stops when either runs out.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IntoPairs;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let pairs: Vec<_> = ["A", "B", "C"]
+    ///     .into_iter()
+    ///     .with_keys(1..)
+    ///     .collect();
+    ///
+    /// assert_eq!(vec![(1, "A"), (2, "B"), (3, "C")], pairs);
+    /// ```
+    fn with_keys<K>(self, keys: K) -> Zip<K::IntoIter, Self>
+    where
+        K: IntoIterator,
+    {
+        keys.into_iter().zip(self)
+    }
+
+    /// Pairs each item, as the key, with the next item from `values`,
+    /// yielding `(T, V)`. Stops when either runs out.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::IntoPairs;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let pairs: Vec<_> = ["a", "b", "c"]
+    ///     .into_iter()
+    ///     .with_values([true, false, true])
+    ///     .collect();
+    ///
+    /// assert_eq!(vec![("a", true), ("b", false), ("c", true)], pairs);
+    /// ```
+    fn with_values<V>(self, values: V) -> Zip<Self, V::IntoIter>
+    where
+        V: IntoIterator,
+    {
+        self.zip(values)
+    }
+
+    /// Turns references to two-element tuples, `&(K, V)`, into tuples of
+    /// references, `(&K, &V)`.
+    ///
+    /// [`IterMap`](crate::IterMap) works with `&(K, V)` directly, and filters
+    /// yield them as they are. This is for when tuples are needed instead,
+    /// like for collecting into a map of references.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::{IntoPairs, IterMap};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let headers = vec![("accept", "text/html"), ("host", "example.com")];
+    ///
+    /// let host: Vec<_> = headers
+    ///     .iter()
+    ///     .pairs()
+    ///     .filter_keys(|k| *k == &"host")
+    ///     .collect();
+    ///
+    /// assert_eq!(vec![(&"host", &"example.com")], host);
+    /// ```
+    fn pairs<'a, K, V>(self) -> Pairs<Self>
+    where
+        Self: Iterator<Item = &'a (K, V)>,
+        K: 'a,
+        V: 'a,
+    {
+        Pairs::new(self)
+    }
+
+    /// Turns mutable references to two-element tuples, `&mut (K, V)`, into
+    /// tuples with a shared reference to the key and a mutable reference to
+    /// the value, `(&K, &mut V)`, like [`HashMap::iter_mut`] yields.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use itermap::{IntoPairs, IterMap};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let mut counts = vec![("a", 1), ("b", 2), ("c", 3)];
+    ///
+    /// counts
+    ///     .iter_mut()
+    ///     .pairs_mut()
+    ///     .filter_keys(|k| *k != &"b")
+    ///     .for_each(|(_, v)| *v *= 10);
+    ///
+    /// assert_eq!(vec![("a", 10), ("b", 2), ("c", 30)], counts);
+    /// ```
+    ///
+    /// [`HashMap::iter_mut`]: std::collections::HashMap::iter_mut
+    fn pairs_mut<'a, K, V>(self) -> PairsMut<Self>
+    where
+        Self: Iterator<Item = &'a mut (K, V)>,
+        K: 'a,
+        V: 'a,
+    {
+        PairsMut::new(self)
+    }
+}
+
+impl<I> IntoPairs for I where I: Iterator {}
+
+/// Pairs up keys and values from two iterables, yielding `(K, V)`. Stops when
+/// either runs out.
+///
+/// # Example
+///
+/// ```
+/// # use std::collections::BTreeMap;
+/// #
+/// use itermap::{pairs_from, IterMap};
+/// # use pretty_assertions::assert_eq;
+///
+/// let map: BTreeMap<_, _> = pairs_from(["a", "b"], [1, 2])
+///     .map_keys(str::to_uppercase)
+///     .collect();
+///
+/// assert_eq!(
+///     BTreeMap::from([(String::from("A"), 1), (String::from("B"), 2)]),
+///     map,
+/// );
+/// ```
+pub fn pairs_from<K, V>(keys: K, values: V) -> Zip<K::IntoIter, V::IntoIter>
+where
+    K: IntoIterator,
+    V: IntoIterator,
+{
+    keys.into_iter().zip(values)
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::IntoPairs;
+    use crate::IterMap;
+
+    #[test]
+    fn double_ended() {
+        let original = vec!["a", "bb", "ccc"];
+
+        // If this doesn't compile, `KeyBy` doesn't implement `DoubleEndedIterator`
+        let keyed: Vec<_> = original
+            .clone()
+            .into_iter()
+            .key_by(|s| s.len())
+            .rev()
+            .collect();
+        assert_eq!(vec![(3, "ccc"), (2, "bb"), (1, "a")], keyed);
+
+        // If this doesn't compile, `ValueBy` doesn't implement `DoubleEndedIterator`
+        let valued: Vec<_> = original.into_iter().value_by(|s| s.len()).rev().collect();
+        assert_eq!(vec![("ccc", 3), ("bb", 2), ("a", 1)], valued);
+    }
+
+    #[test]
+    fn size_hint() {
+        let original = ["a", "bb", "ccc"];
+
+        // If this doesn't compile, `KeyBy` doesn't implement `ExactSizeIterator`
+        let len = original.iter().key_by(|s| s.len()).len();
+        assert_eq!(3, len);
+
+        // If this doesn't compile, `ValueBy` doesn't implement `ExactSizeIterator`
+        let len = original.iter().value_by(|s| s.len()).len();
+        assert_eq!(3, len);
+
+        let iter = original.iter().filter(|s| s.len() > 1).key_by(|s| s.len());
+        assert_eq!((0, Some(3)), iter.size_hint());
+
+        assert_eq!((3, Some(3)), original.iter().with_keys(0..).size_hint());
+        assert_eq!(2, original.iter().with_values([1, 2]).len());
+    }
+
+    #[test]
+    fn chains() {
+        let swapped: Vec<_> = ["a", "bb"]
+            .into_iter()
+            .key_by(|s| s.len())
+            .map_keys(|len| len * 10)
+            .swap()
+            .collect();
+
+        assert_eq!(vec![("a", 10), ("bb", 20)], swapped);
+    }
+
+    #[test]
+    fn pairs() {
+        let mut items = vec![("a", 1), ("b", 2), ("c", 3)];
+
+        let swapped: Vec<_> = items.iter().pairs().swap().rev().collect();
+        assert_eq!(vec![(&3, &"c"), (&2, &"b"), (&1, &"a")], swapped);
+        assert_eq!(3, items.iter().pairs().len());
+        assert_eq!(Some((&"b", &2)), items.iter().pairs().nth(1));
+
+        items
+            .iter_mut()
+            .pairs_mut()
+            .map_keys(|k| k.len())
+            .for_each(|(len, v)| *v += len);
+        assert_eq!(vec![("a", 2), ("b", 3), ("c", 4)], items);
+
+        let mut iter = items.iter_mut().pairs_mut();
+        assert_eq!(Some((&"c", &mut 4)), iter.next_back());
+        assert_eq!(2, iter.get_ref().len());
+    }
+}
@@ -0,0 +1,255 @@
+use core::{
+    fmt,
+    iter::{DoubleEndedIterator, FusedIterator, Iterator},
+};
+
+/// Turns references to two-element tuples, `&(K, V)`, into tuples of
+/// references, `(&K, &V)`.
+///
+/// For use, see the documentation on [`IntoPairs::pairs`].
+///
+/// [`IntoPairs::pairs`]: crate::IntoPairs::pairs
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Pairs<I> {
+    iter: I,
+}
+
+impl<I> Pairs<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        Self { iter }
+    }
+
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items in this adaptor.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the adaptor, returning the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+
+    fn map_item<K, V>((k, v): &(K, V)) -> (&K, &V) {
+        (k, v)
+    }
+}
+
+impl<'a, I, K, V> Iterator for Pairs<I>
+where
+    I: Iterator<Item = &'a (K, V)>,
+    K: 'a,
+    V: 'a,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(Self::map_item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter
+            .fold(init, move |acc, item| f(acc, Self::map_item(item)))
+    }
+
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n).map(Self::map_item)
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.iter.last().map(Self::map_item)
+    }
+}
+
+impl<'a, I, K, V> DoubleEndedIterator for Pairs<I>
+where
+    I: DoubleEndedIterator<Item = &'a (K, V)>,
+    K: 'a,
+    V: 'a,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(Self::map_item)
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter
+            .rfold(init, move |acc, item| f(acc, Self::map_item(item)))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth_back(n).map(Self::map_item)
+    }
+}
+
+impl<'a, I, K, V> ExactSizeIterator for Pairs<I>
+where
+    I: ExactSizeIterator<Item = &'a (K, V)>,
+    K: 'a,
+    V: 'a,
+{
+}
+
+impl<'a, I, K, V> FusedIterator for Pairs<I>
+where
+    I: FusedIterator<Item = &'a (K, V)>,
+    K: 'a,
+    V: 'a,
+{
+}
+
+impl<I> fmt::Debug for Pairs<I>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pairs").field("iter", &self.iter).finish()
+    }
+}
+
+/// Turns mutable references to two-element tuples, `&mut (K, V)`, into
+/// tuples with a shared reference to the key and a mutable reference to the
+/// value, `(&K, &mut V)`.
+///
+/// For use, see the documentation on [`IntoPairs::pairs_mut`].
+///
+/// [`IntoPairs::pairs_mut`]: crate::IntoPairs::pairs_mut
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct PairsMut<I> {
+    iter: I,
+}
+
+impl<I> PairsMut<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        Self { iter }
+    }
+
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items in this adaptor.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the adaptor, returning the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+
+    fn map_item<K, V>((k, v): &mut (K, V)) -> (&K, &mut V) {
+        (k, v)
+    }
+}
+
+impl<'a, I, K, V> Iterator for PairsMut<I>
+where
+    I: Iterator<Item = &'a mut (K, V)>,
+    K: 'a,
+    V: 'a,
+{
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(Self::map_item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter
+            .fold(init, move |acc, item| f(acc, Self::map_item(item)))
+    }
+
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n).map(Self::map_item)
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.iter.last().map(Self::map_item)
+    }
+}
+
+impl<'a, I, K, V> DoubleEndedIterator for PairsMut<I>
+where
+    I: DoubleEndedIterator<Item = &'a mut (K, V)>,
+    K: 'a,
+    V: 'a,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(Self::map_item)
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter
+            .rfold(init, move |acc, item| f(acc, Self::map_item(item)))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth_back(n).map(Self::map_item)
+    }
+}
+
+impl<'a, I, K, V> ExactSizeIterator for PairsMut<I>
+where
+    I: ExactSizeIterator<Item = &'a mut (K, V)>,
+    K: 'a,
+    V: 'a,
+{
+}
+
+impl<'a, I, K, V> FusedIterator for PairsMut<I>
+where
+    I: FusedIterator<Item = &'a mut (K, V)>,
+    K: 'a,
+    V: 'a,
+{
+}
+
+impl<I> fmt::Debug for PairsMut<I>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PairsMut")
+            .field("iter", &self.iter)
+            .finish()
+    }
+}
@@ -0,0 +1,148 @@
+use core::{
+    fmt,
+    iter::{DoubleEndedIterator, FusedIterator, Iterator},
+};
+
+/// Pairs each item with a value computed from it, yielding `(T, V)`.
+///
+/// For use, see the documentation on [`IntoPairs::value_by`].
+///
+/// [`IntoPairs::value_by`]: crate::IntoPairs::value_by
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct ValueBy<I, P> {
+    iter: I,
+    predicate: P,
+}
+
+impl<I, P> ValueBy<I, P> {
+    pub(crate) fn new<V>(iter: I, predicate: P) -> Self
+    where
+        I: Iterator,
+        P: FnMut(&I::Item) -> V,
+    {
+        Self { iter, predicate }
+    }
+
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items in this adaptor.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the adaptor, returning the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I, P> fmt::Debug for ValueBy<I, P>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValueBy")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I, P, V> Iterator for ValueBy<I, P>
+where
+    I: Iterator,
+    P: FnMut(&I::Item) -> V,
+{
+    type Item = (I::Item, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|t| {
+            let v = (self.predicate)(&t);
+            (t, v)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut predicate = self.predicate;
+        self.iter.fold(init, move |acc, t| {
+            let v = predicate(&t);
+            f(acc, (t, v))
+        })
+    }
+
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n).map(|t| {
+            let v = (self.predicate)(&t);
+            (t, v)
+        })
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        let mut predicate = self.predicate;
+        self.iter.last().map(|t| {
+            let v = predicate(&t);
+            (t, v)
+        })
+    }
+}
+
+impl<I, P, V> DoubleEndedIterator for ValueBy<I, P>
+where
+    I: DoubleEndedIterator,
+    P: FnMut(&I::Item) -> V,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|t| {
+            let v = (self.predicate)(&t);
+            (t, v)
+        })
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut predicate = self.predicate;
+        self.iter.rfold(init, move |acc, t| {
+            let v = predicate(&t);
+            f(acc, (t, v))
+        })
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth_back(n).map(|t| {
+            let v = (self.predicate)(&t);
+            (t, v)
+        })
+    }
+}
+
+impl<I, P, V> FusedIterator for ValueBy<I, P>
+where
+    I: FusedIterator,
+    P: FnMut(&I::Item) -> V,
+{
+}
+
+impl<I, P, V> ExactSizeIterator for ValueBy<I, P>
+where
+    I: ExactSizeIterator,
+    P: FnMut(&I::Item) -> V,
+{
+}
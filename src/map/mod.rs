@@ -56,4 +56,104 @@ mod test {
         let len = original.into_iter().map_values(|v| v).len();
         assert_eq!(3, len);
     }
+
+    #[test]
+    fn internal_iteration() {
+        let original = vec![("a", 1), ("b", 2), ("c", 3), ("d", 4)];
+
+        let folded = original
+            .clone()
+            .into_iter()
+            .map_keys(str::to_uppercase)
+            .fold(String::new(), |acc, (k, v)| format!("{acc}{k}{v}"));
+        assert_eq!("A1B2C3D4", folded);
+
+        let folded = original
+            .clone()
+            .into_iter()
+            .map_values(|v| v * 10)
+            .rfold(String::new(), |acc, (k, v)| format!("{acc}{k}{v}"));
+        assert_eq!("d40c30b20a10", folded);
+
+        let mut iter = original.clone().into_iter().map_keys(str::to_uppercase);
+        assert_eq!(Some((String::from("B"), 2)), iter.nth(1));
+        assert_eq!(Some((String::from("D"), 4)), iter.nth_back(0));
+        assert_eq!(Some((String::from("C"), 3)), iter.last());
+
+        let mut iter = original.clone().into_iter().map_values(|v| v * 10);
+        assert_eq!(Some(("c", 30)), iter.nth_back(1));
+        assert_eq!(None, iter.nth(2));
+    }
+
+    #[test]
+    fn skips_closure() {
+        let original = vec![("a", 1), ("b", 2), ("c", 3)];
+        let mut calls = 0;
+
+        let count = original
+            .clone()
+            .into_iter()
+            .map_keys(|k| {
+                calls += 1;
+                k
+            })
+            .count();
+        assert_eq!(3, count);
+        assert_eq!(0, calls);
+
+        let last = original
+            .into_iter()
+            .map_values(|v| {
+                calls += 1;
+                v
+            })
+            .last();
+        assert_eq!(Some(("c", 3)), last);
+        assert_eq!(1, calls);
+    }
+
+    #[test]
+    fn clone() {
+        let original = vec![("a", 1), ("b", 2), ("c", 3)];
+
+        // If this doesn't compile, the adaptors don't implement `Clone`
+        let mut iter = original
+            .into_iter()
+            .map_keys(str::to_uppercase)
+            .filter_values(|v| v % 2 == 1)
+            .map_values(|v| v * 10)
+            .swap();
+
+        assert_eq!(Some((10, String::from("A"))), iter.next());
+
+        let copy = iter.clone();
+        assert_eq!(vec![(30, String::from("C"))], iter.collect::<Vec<_>>());
+        assert_eq!(vec![(30, String::from("C"))], copy.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn inner() {
+        let mut iter = vec![("a", 1), ("b", 2), ("c", 3)]
+            .into_iter()
+            .map_values(|v| v * 10);
+
+        assert_eq!(3, iter.get_ref().len());
+        assert_eq!(Some(("a", 1)), iter.get_mut().next());
+        assert_eq!(Some(("b", 20)), iter.next());
+        assert_eq!(vec![("c", 3)], iter.into_inner().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn debug() {
+        let iter = [("a", 1)].into_iter();
+
+        assert_eq!(
+            "MapKeys { iter: IntoIter([(\"a\", 1)]), .. }",
+            format!("{:?}", iter.clone().map_keys(|k| k)),
+        );
+        assert_eq!(
+            "FilterValues { iter: IntoIter([(\"a\", 1)]), .. }",
+            format!("{:?}", iter.filter_values(|_| true)),
+        );
+    }
 }
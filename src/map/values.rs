@@ -1,6 +1,9 @@
-use core::iter::{DoubleEndedIterator, FusedIterator, Iterator};
+use core::{
+    fmt,
+    iter::{DoubleEndedIterator, FusedIterator, Iterator},
+};
 
-use crate::iter::Iter;
+use crate::KeyValue;
 
 /// Maps values, or the second element of a two-element tuple (like `(K, V)`),
 /// leaving the other element intact and untouched.
@@ -9,58 +12,145 @@ use crate::iter::Iter;
 ///
 /// [`IterMap::map_values`]: crate::IterMap::map_values
 #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
-#[derive(Debug)]
-pub struct MapValues<I, P>(Iter<I, P>);
+#[derive(Clone)]
+pub struct MapValues<I, P> {
+    iter: I,
+    predicate: P,
+}
 
 impl<I, P> MapValues<I, P> {
     pub(crate) fn new<K, V, W>(iter: I, predicate: P) -> Self
     where
-        I: Iterator<Item = (K, V)>,
+        I: Iterator,
+        I::Item: KeyValue<Key = K, Value = V>,
         P: FnMut(V) -> W,
     {
-        Self(Iter { iter, predicate })
+        Self { iter, predicate }
+    }
+
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items in this adaptor.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the adaptor, returning the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I, P> fmt::Debug for MapValues<I, P>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapValues")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
     }
 }
 
 impl<I, P, K, V, W> Iterator for MapValues<I, P>
 where
-    I: Iterator<Item = (K, V)>,
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
     P: FnMut(V) -> W,
 {
     type Item = (K, W);
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.iter.next().map(|(k, v)| (k, (self.0.predicate)(v)))
+        self.iter
+            .next()
+            .map(KeyValue::into_parts)
+            .map(|(k, v)| (k, (self.predicate)(v)))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.0.iter.size_hint()
+        self.iter.size_hint()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut predicate = self.predicate;
+        self.iter.fold(init, move |acc, item| {
+            let (k, v) = item.into_parts();
+            f(acc, (k, predicate(v)))
+        })
+    }
+
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter
+            .nth(n)
+            .map(KeyValue::into_parts)
+            .map(|(k, v)| (k, (self.predicate)(v)))
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        let mut predicate = self.predicate;
+        self.iter
+            .last()
+            .map(KeyValue::into_parts)
+            .map(|(k, v)| (k, predicate(v)))
     }
 }
 
 impl<I, P, K, V, W> DoubleEndedIterator for MapValues<I, P>
 where
-    I: DoubleEndedIterator<Item = (K, V)>,
+    I: DoubleEndedIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
     P: FnMut(V) -> W,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
-        self.0
-            .iter
+        self.iter
             .next_back()
-            .map(|(k, v)| (k, (self.0.predicate)(v)))
+            .map(KeyValue::into_parts)
+            .map(|(k, v)| (k, (self.predicate)(v)))
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut predicate = self.predicate;
+        self.iter.rfold(init, move |acc, item| {
+            let (k, v) = item.into_parts();
+            f(acc, (k, predicate(v)))
+        })
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter
+            .nth_back(n)
+            .map(KeyValue::into_parts)
+            .map(|(k, v)| (k, (self.predicate)(v)))
     }
 }
 
 impl<I, P, K, V, W> FusedIterator for MapValues<I, P>
 where
-    I: FusedIterator<Item = (K, V)>,
+    I: FusedIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
     P: FnMut(V) -> W,
 {
 }
 
 impl<I, P, K, V, W> ExactSizeIterator for MapValues<I, P>
 where
-    I: ExactSizeIterator<Item = (K, V)>,
+    I: ExactSizeIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
     P: FnMut(V) -> W,
 {
 }
@@ -0,0 +1,377 @@
+use core::{fmt, iter::FusedIterator};
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+
+use crate::KeyValue;
+
+/// Flattens nested maps, yielding `((K, K2), V2)` for each item of each
+/// value.
+///
+/// See: [`IterMap::flatten_nested`]
+///
+/// [`IterMap::flatten_nested`]: crate::IterMap::flatten_nested
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct FlattenNested<I, K, J> {
+    iter: I,
+    inner: Option<(K, J)>,
+}
+
+impl<I, K, J> FlattenNested<I, K, J> {
+    pub(crate) fn new(iter: I) -> Self {
+        Self { iter, inner: None }
+    }
+
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items in this adaptor.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the adaptor, returning the underlying iterator. Any remaining
+    /// items of the value currently being flattened are dropped.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I, K, V, J, K2, V2> Iterator for FlattenNested<I, K, J>
+where
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    V: IntoIterator<IntoIter = J>,
+    J: Iterator,
+    J::Item: KeyValue<Key = K2, Value = V2>,
+    K: Clone,
+{
+    type Item = ((K, K2), V2);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((k, inner)) = &mut self.inner {
+                if let Some(item) = inner.next() {
+                    let (k2, v2) = item.into_parts();
+                    return Some(((k.clone(), k2), v2));
+                }
+            }
+
+            let (k, v) = self.iter.next()?.into_parts();
+            self.inner = Some((k, v.into_iter()));
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let lower = self
+            .inner
+            .as_ref()
+            .map_or(0, |(_, inner)| inner.size_hint().0);
+
+        // Any value left in the outer iterator could have any number of items.
+        match self.iter.size_hint() {
+            (_, Some(0)) => (
+                lower,
+                self.inner
+                    .as_ref()
+                    .map_or(Some(0), |(_, inner)| inner.size_hint().1),
+            ),
+            _ => (lower, None),
+        }
+    }
+}
+
+impl<I, K, V, J, K2, V2> FusedIterator for FlattenNested<I, K, J>
+where
+    I: FusedIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    V: IntoIterator<IntoIter = J>,
+    J: Iterator,
+    J::Item: KeyValue<Key = K2, Value = V2>,
+    K: Clone,
+{
+}
+
+impl<I, K, J> Clone for FlattenNested<I, K, J>
+where
+    I: Clone,
+    K: Clone,
+    J: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<I, K, J> fmt::Debug for FlattenNested<I, K, J>
+where
+    I: fmt::Debug,
+    K: fmt::Debug,
+    J: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlattenNested")
+            .field("iter", &self.iter)
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+/// Maps that can have values inserted at composite keys, creating nested maps
+/// as needed. Used by [`IterMap::unflatten_into`].
+///
+/// Implemented for [`HashMap`] and [`BTreeMap`], nested to any depth. A map
+/// with values of type `V` takes keys of its own key type. A map with nested
+/// map values takes keys of `(K, K2)`, where `K2` is the key the nested map
+/// takes. So `HashMap<A, HashMap<B, BTreeMap<C, V>>>` takes `(A, (B, C))`.
+///
+/// Later values replace earlier ones with the same composite key.
+///
+/// [`IterMap::unflatten_into`]: crate::IterMap::unflatten_into
+/// [`HashMap`]: std::collections::HashMap
+/// [`BTreeMap`]: std::collections::BTreeMap
+#[cfg(feature = "alloc")]
+pub trait Unflatten<K, V> {
+    /// Inserts `value` at the composite `key`.
+    fn insert_nested(&mut self, key: K, value: V);
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> Unflatten<K, V> for alloc::collections::BTreeMap<K, V>
+where
+    K: Ord,
+{
+    fn insert_nested(&mut self, key: K, value: V) {
+        self.insert(key, value);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, K2, M, V> Unflatten<(K, K2), V> for alloc::collections::BTreeMap<K, M>
+where
+    K: Ord,
+    M: Unflatten<K2, V> + Default,
+{
+    fn insert_nested(&mut self, (key, key2): (K, K2), value: V) {
+        self.entry(key).or_default().insert_nested(key2, value);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> Unflatten<K, V> for std::collections::HashMap<K, V, S>
+where
+    K: Eq + core::hash::Hash,
+    S: core::hash::BuildHasher,
+{
+    fn insert_nested(&mut self, key: K, value: V) {
+        self.insert(key, value);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, K2, M, V, S> Unflatten<(K, K2), V> for std::collections::HashMap<K, M, S>
+where
+    K: Eq + core::hash::Hash,
+    M: Unflatten<K2, V> + Default,
+    S: core::hash::BuildHasher,
+{
+    fn insert_nested(&mut self, (key, key2): (K, K2), value: V) {
+        self.entry(key).or_default().insert_nested(key2, value);
+    }
+}
+
+/// A dense table of values, with every cell filled. Returned by
+/// [`IterMap::transpose_dense`].
+///
+/// [`IterMap::transpose_dense`]: crate::IterMap::transpose_dense
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DenseTable<R, C, V> {
+    /// The row labels, in ascending order.
+    pub rows: Vec<R>,
+    /// The column labels, in ascending order.
+    pub columns: Vec<C>,
+    /// One `Vec` per row, each with one value per column.
+    pub cells: Vec<Vec<V>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<R, C, V> DenseTable<R, C, V> {
+    /// Transposes `iter`, filling cells it has no value for with `fill`.
+    pub(crate) fn transposed<I, J>(iter: I, fill: V) -> Self
+    where
+        I: Iterator,
+        I::Item: KeyValue<Key = C, Value = J>,
+        J: IntoIterator,
+        J::Item: KeyValue<Key = R, Value = V>,
+        R: Ord,
+        C: Ord + Clone,
+        V: Clone,
+    {
+        let mut columns = BTreeSet::new();
+        let mut by_row = BTreeMap::<R, BTreeMap<C, V>>::new();
+
+        for (column, values) in iter.map(KeyValue::into_parts) {
+            for (row, value) in values.into_iter().map(KeyValue::into_parts) {
+                by_row.entry(row).or_default().insert(column.clone(), value);
+            }
+            columns.insert(column);
+        }
+
+        let columns: Vec<C> = columns.into_iter().collect();
+        let (rows, cells) = by_row
+            .into_iter()
+            .map(|(row, mut values)| {
+                let values = columns
+                    .iter()
+                    .map(|column| values.remove(column).unwrap_or_else(|| fill.clone()))
+                    .collect();
+                (row, values)
+            })
+            .unzip();
+
+        Self {
+            rows,
+            columns,
+            cells,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use std::collections::{BTreeMap, HashMap};
+
+    use pretty_assertions::assert_eq;
+
+    use super::DenseTable;
+    use crate::IterMap;
+
+    fn nested() -> BTreeMap<&'static str, BTreeMap<&'static str, u32>> {
+        BTreeMap::from([
+            ("acme", BTreeMap::from([("limit", 10), ("used", 3)])),
+            ("empty", BTreeMap::new()),
+            ("initech", BTreeMap::from([("limit", 5)])),
+        ])
+    }
+
+    #[test]
+    fn flatten() {
+        let rows: Vec<_> = nested().into_iter().flatten_nested().collect();
+
+        assert_eq!(
+            vec![
+                (("acme", "limit"), 10),
+                (("acme", "used"), 3),
+                (("initech", "limit"), 5),
+            ],
+            rows
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let map: BTreeMap<_, BTreeMap<_, _>> =
+            nested().into_iter().flatten_nested().unflatten_into();
+
+        let mut expected = nested();
+        expected.remove("empty");
+        assert_eq!(expected, map);
+
+        let map: HashMap<_, HashMap<_, _>> = nested().into_iter().flatten_nested().unflatten_into();
+        assert_eq!(Some(&3), map["acme"].get("used"));
+    }
+
+    #[test]
+    fn three_levels() {
+        let map = BTreeMap::from([
+            ('a', BTreeMap::from([(1, BTreeMap::from([("x", true)]))])),
+            (
+                'b',
+                BTreeMap::from([(2, BTreeMap::from([("y", false), ("z", true)]))]),
+            ),
+        ]);
+
+        let rows: Vec<_> = map
+            .clone()
+            .into_iter()
+            .map_values(|inner| inner.into_iter().flatten_nested())
+            .flatten_nested()
+            .collect();
+        assert_eq!(
+            vec![
+                (('a', (1, "x")), true),
+                (('b', (2, "y")), false),
+                (('b', (2, "z")), true),
+            ],
+            rows
+        );
+
+        let unflattened: BTreeMap<_, BTreeMap<_, BTreeMap<_, _>>> =
+            rows.into_iter().unflatten_into();
+        assert_eq!(map, unflattened);
+    }
+
+    #[test]
+    fn transpose() {
+        let by_column: HashMap<_, HashMap<_, _>> = nested().into_iter().transpose_nested();
+        assert_eq!(2, by_column.len());
+        assert_eq!(
+            HashMap::from([("acme", 10), ("initech", 5)]),
+            by_column["limit"]
+        );
+        assert_eq!(HashMap::from([("acme", 3)]), by_column["used"]);
+
+        let twice: BTreeMap<_, BTreeMap<_, _>> = by_column.into_iter().transpose_nested();
+        let mut expected = nested();
+        expected.remove("empty");
+        assert_eq!(expected, twice);
+    }
+
+    #[test]
+    fn transpose_dense() {
+        let table = nested().into_iter().transpose_dense(0);
+
+        assert_eq!(
+            DenseTable {
+                rows: vec!["limit", "used"],
+                columns: vec!["acme", "empty", "initech"],
+                cells: vec![vec![10, 0, 5], vec![3, 0, 0]],
+            },
+            table
+        );
+
+        let empty = Vec::<(u8, Vec<(u8, u8)>)>::new()
+            .into_iter()
+            .transpose_dense(0);
+        assert!(empty.rows.is_empty() && empty.columns.is_empty() && empty.cells.is_empty());
+    }
+
+    #[test]
+    fn size_hint() {
+        let mut iter = nested().into_iter().flatten_nested();
+        assert_eq!((0, None), iter.size_hint());
+
+        iter.by_ref().take(2).for_each(drop);
+        assert_eq!((0, None), iter.size_hint());
+
+        let mut iter = BTreeMap::from([("acme", BTreeMap::from([("cpu", 4), ("disk", 100)]))])
+            .into_iter()
+            .flatten_nested();
+        iter.next();
+        assert_eq!((1, Some(1)), iter.size_hint());
+
+        iter.next();
+        assert_eq!((0, Some(0)), iter.size_hint());
+    }
+}
@@ -0,0 +1,364 @@
+use alloc::vec::{self, Vec};
+use core::{fmt, hash::Hash, slice};
+use std::collections::HashMap;
+
+use crate::KeyValue;
+
+/// Maps keys, the first element of a two-element tuple (like `(K, V)`), while
+/// detecting when two original keys map to the same normalized key. Call one
+/// of the methods to pick how those collisions are handled.
+///
+/// Items are yielded in the order their normalized keys were first seen.
+///
+/// See: [`IterMap::normalize_keys`]
+///
+/// [`IterMap::normalize_keys`]: crate::IterMap::normalize_keys
+#[must_use = "call a method to pick how collisions are handled"]
+pub struct NormalizeKeys<I, P> {
+    iter: I,
+    key_op: P,
+}
+
+impl<I, P> NormalizeKeys<I, P> {
+    pub(crate) fn new(iter: I, key_op: P) -> Self {
+        Self { iter, key_op }
+    }
+}
+
+impl<I, P, K, L, V> NormalizeKeys<I, P>
+where
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    P: FnMut(&K) -> L,
+    L: Eq + Hash + Clone,
+{
+    /// Keeps the value of the first item for each normalized key.
+    pub fn keep_first(self) -> Normalized<K, L, V>
+    where
+        K: Clone,
+    {
+        self.resolve_all(|first, _| first)
+    }
+
+    /// Keeps the value of the last item for each normalized key. The item is
+    /// still yielded at the position its normalized key was first seen.
+    pub fn keep_last(self) -> Normalized<K, L, V>
+    where
+        K: Clone,
+    {
+        self.resolve_all(|_, last| last)
+    }
+
+    /// Combines the values of items with the same normalized key, in the
+    /// order they're seen.
+    pub fn combine<F>(self, f: F) -> Normalized<K, L, V>
+    where
+        K: Clone,
+        F: FnMut(V, V) -> V,
+    {
+        self.resolve_all(f)
+    }
+
+    /// Returns an error with both original keys on the first collision.
+    pub fn require_unique(self) -> Result<Normalized<K, L, V>, KeyCollision<K, L>> {
+        let entries = self.resolve(|_, _| None, |_, _, _| {})?;
+
+        Ok(Normalized {
+            entries,
+            collisions: Vec::new(),
+        })
+    }
+
+    fn resolve_all<F>(self, mut f: F) -> Normalized<K, L, V>
+    where
+        K: Clone,
+        F: FnMut(V, V) -> V,
+    {
+        let mut collisions = Vec::new();
+
+        let entries = self.resolve(
+            |first, second| Some(f(first, second)),
+            |first, second, key| {
+                collisions.push(KeyCollision {
+                    key,
+                    first: first.clone(),
+                    second,
+                });
+            },
+        );
+
+        match entries {
+            Ok(entries) => Normalized {
+                entries,
+                collisions,
+            },
+            Err(_) => unreachable!("all collisions were resolved"),
+        }
+    }
+
+    /// Normalizes all keys. On a collision, `resolve` is called with the
+    /// current and new values. If it returns `None`, normalization stops with
+    /// an error. Otherwise `collided` is called with the original keys.
+    fn resolve<R, C>(
+        mut self,
+        mut resolve: R,
+        mut collided: C,
+    ) -> Result<Vec<(L, V)>, KeyCollision<K, L>>
+    where
+        R: FnMut(V, V) -> Option<V>,
+        C: FnMut(&K, K, L),
+    {
+        let mut positions = HashMap::<L, usize>::new();
+        let mut originals = Vec::<K>::new();
+        let mut entries = Vec::<(L, Option<V>)>::new();
+
+        for (k, v) in self.iter.map(KeyValue::into_parts) {
+            let key = (self.key_op)(&k);
+
+            let Some(&position) = positions.get(&key) else {
+                positions.insert(key.clone(), entries.len());
+                originals.push(k);
+                entries.push((key, Some(v)));
+                continue;
+            };
+
+            let slot = &mut entries[position].1;
+            let current = slot.take().expect("values are always put back");
+
+            let Some(value) = resolve(current, v) else {
+                return Err(KeyCollision {
+                    key,
+                    first: originals.swap_remove(position),
+                    second: k,
+                });
+            };
+
+            *slot = Some(value);
+            collided(&originals[position], k, key);
+        }
+
+        Ok(entries
+            .into_iter()
+            .map(|(key, value)| (key, value.expect("values are always put back")))
+            .collect())
+    }
+}
+
+impl<I, P> fmt::Debug for NormalizeKeys<I, P>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NormalizeKeys")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Two original keys that normalized to the same key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyCollision<K, L> {
+    /// The normalized key.
+    pub key: L,
+    /// The original key that was seen first.
+    pub first: K,
+    /// The original key that was seen later.
+    pub second: K,
+}
+
+impl<K, L> fmt::Display for KeyCollision<K, L>
+where
+    K: fmt::Debug,
+    L: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "keys {:?} and {:?} both normalize to {:?}",
+            self.first, self.second, self.key
+        )
+    }
+}
+
+impl<K, L> std::error::Error for KeyCollision<K, L>
+where
+    K: fmt::Debug,
+    L: fmt::Debug,
+{
+}
+
+/// The result of [`IterMap::normalize_keys`]: the items with normalized keys,
+/// and a report of the collisions that were resolved.
+///
+/// Iterate over it to get the items.
+///
+/// [`IterMap::normalize_keys`]: crate::IterMap::normalize_keys
+#[derive(Debug, Clone)]
+pub struct Normalized<K, L, V> {
+    entries: Vec<(L, V)>,
+    collisions: Vec<KeyCollision<K, L>>,
+}
+
+impl<K, L, V> Normalized<K, L, V> {
+    /// The collisions that were resolved, in the order they were seen.
+    pub fn collisions(&self) -> &[KeyCollision<K, L>] {
+        &self.collisions
+    }
+
+    /// The number of items, one for each normalized key.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// An iterator over `(&L, &V)` for each normalized key.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&L, &V)> + ExactSizeIterator {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+
+    /// Takes the collisions out of the report, leaving it empty. Useful for
+    /// keeping the collisions after iterating over the items.
+    pub fn take_collisions(&mut self) -> Vec<KeyCollision<K, L>> {
+        core::mem::take(&mut self.collisions)
+    }
+}
+
+impl<K, L, V> IntoIterator for Normalized<K, L, V> {
+    type Item = (L, V);
+    type IntoIter = vec::IntoIter<(L, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a, K, L, V> IntoIterator for &'a Normalized<K, L, V> {
+    type Item = &'a (L, V);
+    type IntoIter = slice::Iter<'a, (L, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use pretty_assertions::assert_eq;
+
+    use super::KeyCollision;
+    use crate::IterMap;
+
+    fn headers() -> Vec<(&'static str, u32)> {
+        vec![
+            ("Content-Type", 1),
+            ("Accept", 2),
+            ("content-type", 3),
+            ("CONTENT-TYPE", 4),
+        ]
+    }
+
+    #[test]
+    fn keep_first() {
+        let normalized = headers()
+            .into_iter()
+            .normalize_keys(|k| k.to_lowercase())
+            .keep_first();
+
+        assert_eq!(
+            &[
+                KeyCollision {
+                    key: String::from("content-type"),
+                    first: "Content-Type",
+                    second: "content-type",
+                },
+                KeyCollision {
+                    key: String::from("content-type"),
+                    first: "Content-Type",
+                    second: "CONTENT-TYPE",
+                },
+            ],
+            normalized.collisions()
+        );
+
+        assert_eq!(
+            vec![
+                (String::from("content-type"), 1),
+                (String::from("accept"), 2)
+            ],
+            normalized.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn keep_last() {
+        let mut normalized = headers()
+            .into_iter()
+            .normalize_keys(|k| k.to_lowercase())
+            .keep_last();
+
+        assert_eq!(2, normalized.take_collisions().len());
+        assert!(normalized.collisions().is_empty());
+        assert_eq!(
+            vec![
+                (String::from("content-type"), 4),
+                (String::from("accept"), 2)
+            ],
+            normalized.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn combine() {
+        let map: HashMap<_, _> = headers()
+            .into_iter()
+            .normalize_keys(|k| k.to_lowercase())
+            .combine(|a, b| a + b)
+            .into_iter()
+            .collect();
+
+        assert_eq!(
+            HashMap::from([
+                (String::from("content-type"), 8),
+                (String::from("accept"), 2)
+            ]),
+            map
+        );
+    }
+
+    #[test]
+    fn require_unique() {
+        let err = headers()
+            .into_iter()
+            .normalize_keys(|k| k.to_lowercase())
+            .require_unique()
+            .unwrap_err();
+
+        assert_eq!(
+            KeyCollision {
+                key: String::from("content-type"),
+                first: "Content-Type",
+                second: "content-type",
+            },
+            err
+        );
+        assert_eq!(
+            r#"keys "Content-Type" and "content-type" both normalize to "content-type""#,
+            err.to_string()
+        );
+
+        let normalized = vec![("A", 1), ("b", 2)]
+            .into_iter()
+            .normalize_keys(|k| k.to_lowercase())
+            .require_unique()
+            .unwrap();
+
+        assert!(normalized.collisions().is_empty());
+        assert_eq!(2, normalized.len());
+    }
+}
@@ -0,0 +1,673 @@
+//! Reading and writing `key=value` lines, like `.env` files and Java-style
+//! `.properties` files, as `Iterator`s over two-element tuples (like
+//! `(K, V)`).
+//!
+//! Use [`parse`] to read pairs and [`encode`] to write them. Anything written
+//! by [`encode`] reads back the same with [`parse`]. Empty keys can't be read,
+//! so [`encode`] returns an error for them.
+//!
+//! # Format
+//!
+//! * Each entry is a key and a value separated by the first unescaped `=` or
+//!   `:`. Whitespace around the key and value is ignored: spaces, tabs, and
+//!   form feeds, but not other Unicode whitespace.
+//! * Blank lines and lines starting with `#` or `!` are comments. In unquoted
+//!   values, a `#` after whitespace starts a comment.
+//! * Keys may be prefixed with `export `, as in shell scripts.
+//! * `\` escapes the next character. `\n`, `\r`, `\t`, and `\uXXXX` are
+//!   recognized; any other escaped character stands for itself.
+//! * In keys and unquoted values, a `\` at the end of a line continues the
+//!   entry on the next line, ignoring that line's leading whitespace.
+//! * Values may be wrapped in `"double quotes"`, which may span lines and
+//!   recognize escapes, or `'single quotes'`, which are taken literally.
+//!
+//! # Example
+//!
+//! ```
+//! use itermap::properties;
+//! # use pretty_assertions::assert_eq;
+//!
+//! let text = r#"
+//! ## Database settings
+//! export DB_HOST=localhost
+//! DB_PASSWORD="p@ss # not a comment"
+//! DB_PORT = 5432 # a comment
+//! "#;
+//!
+//! let pairs: Vec<_> = properties::parse(text)
+//!     .collect::<Result<_, _>>()
+//!     .unwrap();
+//!
+//! assert_eq!(
+//!     vec![
+//!         ("DB_HOST".into(), "localhost".into()),
+//!         ("DB_PASSWORD".into(), "p@ss # not a comment".into()),
+//!         ("DB_PORT".into(), "5432".into()),
+//!     ],
+//!     pairs,
+//! );
+//!
+//! let text = properties::encode(pairs.into_iter().take(2))
+//!     .unwrap()
+//!     .to_string();
+//! assert_eq!("DB_HOST=localhost\nDB_PASSWORD=p@ss \\# not a comment\n", text);
+//! ```
+
+use alloc::{borrow::Cow, string::String};
+use core::{
+    fmt::{self, Write},
+    iter::FusedIterator,
+};
+
+use crate::KeyValue;
+
+/// Parses `key=value` lines into `(key, value)` pairs. See the [module
+/// documentation](self) for the format.
+///
+/// Keys and values are borrowed from `text` unless they had to be unescaped.
+/// Entries that can't be parsed are yielded as a [`ParseError`] with the line
+/// they started on, and parsing continues with the next line.
+pub fn parse(text: &str) -> Parse<'_> {
+    Parse {
+        rest: text,
+        line: 0,
+    }
+}
+
+/// An `Iterator` over the `(key, value)` pairs in `key=value` lines.
+///
+/// See: [`parse`]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Debug, Clone)]
+pub struct Parse<'a> {
+    rest: &'a str,
+    line: usize,
+}
+
+type Pair<'a> = (Cow<'a, str>, Cow<'a, str>);
+
+/// The whitespace that's ignored around keys and values, as in `.properties`
+/// files. Other whitespace, like `\u{A0}`, is part of the key or value.
+const WHITESPACE: [char; 3] = [' ', '\t', '\x0C'];
+
+impl<'a> Parse<'a> {
+    fn next_line(&mut self) -> Option<&'a str> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        let (line, rest) = self.rest.split_once('\n').unwrap_or((self.rest, ""));
+        self.rest = rest;
+        self.line += 1;
+
+        Some(line.strip_suffix('\r').unwrap_or(line))
+    }
+
+    fn entry(&mut self, text: &'a str) -> Result<Pair<'a>, ErrorKind> {
+        let text = match text.strip_prefix("export") {
+            Some(rest)
+                if rest.starts_with(WHITESPACE)
+                    && !rest.trim_start_matches(WHITESPACE).starts_with(['=', ':']) =>
+            {
+                rest.trim_start_matches(WHITESPACE)
+            }
+            _ => text,
+        };
+
+        let (key, value) = self.key(text)?;
+        if key.is_empty() {
+            return Err(ErrorKind::EmptyKey);
+        }
+
+        let trimmed = value.trim_start_matches(WHITESPACE);
+        let value = if let Some(quoted) = trimmed.strip_prefix('"') {
+            self.double_quoted(quoted)?
+        } else if let Some(quoted) = trimmed.strip_prefix('\'') {
+            let (value, rest) = quoted
+                .split_once('\'')
+                .ok_or(ErrorKind::UnterminatedQuote)?;
+            check_trailing(rest)?;
+            Cow::Borrowed(value)
+        } else {
+            self.unquoted(trimmed, trimmed.len() < value.len())?
+        };
+
+        Ok((key, value))
+    }
+
+    /// Reads the key, returning it and the text after the separator.
+    fn key(&mut self, mut text: &'a str) -> Result<(Cow<'a, str>, &'a str), ErrorKind> {
+        let mut key: Option<Unescaped> = None;
+
+        loop {
+            let (segment, end) = scan(text, |c, _| matches!(c, '=' | ':'));
+
+            match end {
+                End::Stop(i) => {
+                    let after = &text[i + 1..];
+
+                    if key.is_none() && !segment.contains('\\') {
+                        return Ok((Cow::Borrowed(segment.trim_end_matches(WHITESPACE)), after));
+                    }
+
+                    let mut key = key.unwrap_or_default();
+                    key.push(segment)?;
+                    return Ok((Cow::Owned(key.finish()), after));
+                }
+                End::Continued => {
+                    key.get_or_insert_with(Unescaped::default).push(segment)?;
+                    text = self
+                        .next_line()
+                        .ok_or(ErrorKind::MissingSeparator)?
+                        .trim_start_matches(WHITESPACE);
+                }
+                End::Line => return Err(ErrorKind::MissingSeparator),
+            }
+        }
+    }
+
+    /// Reads an unquoted value. `leading_space` is whether there was
+    /// whitespace before the value, so a `#` at the start is a comment.
+    fn unquoted(
+        &mut self,
+        mut text: &'a str,
+        leading_space: bool,
+    ) -> Result<Cow<'a, str>, ErrorKind> {
+        let mut value: Option<Unescaped> = None;
+        let mut leading_space = leading_space;
+
+        loop {
+            let (segment, end) = scan(text, |c, prev| {
+                c == '#' && prev.map_or(leading_space, |p| WHITESPACE.contains(&p))
+            });
+
+            if value.is_none() && !matches!(end, End::Continued) && !segment.contains('\\') {
+                return Ok(Cow::Borrowed(segment.trim_end_matches(WHITESPACE)));
+            }
+
+            let value = value.get_or_insert_with(Unescaped::default);
+            value.push(segment)?;
+
+            if !matches!(end, End::Continued) {
+                return Ok(Cow::Owned(core::mem::take(value).finish()));
+            }
+
+            text = self
+                .next_line()
+                .unwrap_or_default()
+                .trim_start_matches(WHITESPACE);
+            leading_space = false;
+        }
+    }
+
+    /// Reads a double-quoted value. `text` is everything after the opening
+    /// quote.
+    fn double_quoted(&mut self, mut text: &'a str) -> Result<Cow<'a, str>, ErrorKind> {
+        let mut value: Option<Unescaped> = None;
+
+        loop {
+            let (segment, end) = scan(text, |c, _| c == '"');
+
+            if let End::Stop(i) = end {
+                check_trailing(&text[i + 1..])?;
+
+                if value.is_none() && !segment.contains('\\') {
+                    return Ok(Cow::Borrowed(segment));
+                }
+
+                let mut value = value.unwrap_or_default();
+                value.push(segment)?;
+                return Ok(Cow::Owned(value.into_string()));
+            }
+
+            let value = value.get_or_insert_with(Unescaped::default);
+            value.push(segment)?;
+            if let End::Line = end {
+                value.out.push('\n');
+            }
+
+            text = self.next_line().ok_or(ErrorKind::UnterminatedQuote)?;
+        }
+    }
+}
+
+impl<'a> Iterator for Parse<'a> {
+    type Item = Result<Pair<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let text = self.next_line()?.trim_start_matches(WHITESPACE);
+            if text.is_empty() || text.starts_with(['#', '!']) {
+                continue;
+            }
+
+            let line = self.line;
+            return Some(self.entry(text).map_err(|kind| ParseError { line, kind }));
+        }
+    }
+}
+
+impl FusedIterator for Parse<'_> {}
+
+/// How a scanned segment of a line ended.
+enum End {
+    /// At the index of the character the scan stopped at.
+    Stop(usize),
+    /// At an escaping `\` at the end of the line.
+    Continued,
+    /// At the end of the line.
+    Line,
+}
+
+/// Scans `text` for the first unescaped character `stop` returns `true` for,
+/// given that character and the previous one. Returns the text before it and
+/// how the scan ended.
+fn scan<F>(text: &str, mut stop: F) -> (&str, End)
+where
+    F: FnMut(char, Option<char>) -> bool,
+{
+    let mut escaped = false;
+    let mut prev = None;
+
+    for (i, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if stop(c, prev) {
+            return (&text[..i], End::Stop(i));
+        }
+
+        prev = Some(c);
+    }
+
+    if escaped {
+        (&text[..text.len() - 1], End::Continued)
+    } else {
+        (text, End::Line)
+    }
+}
+
+/// Only whitespace or a comment may follow a quoted value.
+fn check_trailing(rest: &str) -> Result<(), ErrorKind> {
+    let rest = rest.trim_start_matches(WHITESPACE);
+    if rest.is_empty() || rest.starts_with('#') {
+        Ok(())
+    } else {
+        Err(ErrorKind::TrailingCharacters)
+    }
+}
+
+/// Builds an unescaped key or value.
+#[derive(Default)]
+struct Unescaped {
+    out: String,
+    /// The length of `out` after the last escaped character, so escaped
+    /// trailing whitespace isn't trimmed.
+    keep: usize,
+}
+
+impl Unescaped {
+    fn push(&mut self, text: &str) -> Result<(), ErrorKind> {
+        let mut chars = text.chars();
+
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                self.out.push(c);
+                continue;
+            }
+
+            let escaped = match chars.next() {
+                Some('n') => '\n',
+                Some('r') => '\r',
+                Some('t') => '\t',
+                Some('u') => {
+                    let hex = chars
+                        .as_str()
+                        .get(..4)
+                        .filter(|hex| hex.chars().all(|c| c.is_ascii_hexdigit()))
+                        .ok_or(ErrorKind::InvalidEscape)?;
+                    let code =
+                        u32::from_str_radix(hex, 16).map_err(|_| ErrorKind::InvalidEscape)?;
+                    chars = chars.as_str()[4..].chars();
+                    char::from_u32(code).ok_or(ErrorKind::InvalidEscape)?
+                }
+                Some(c) => c,
+                // `scan` strips escaping backslashes at the end of lines.
+                None => unreachable!("`\\` at the end of a segment"),
+            };
+
+            self.out.push(escaped);
+            self.keep = self.out.len();
+        }
+
+        Ok(())
+    }
+
+    /// Returns the text, with unescaped trailing whitespace trimmed.
+    fn finish(mut self) -> String {
+        let len = self.keep + self.out[self.keep..].trim_end_matches(WHITESPACE).len();
+        self.out.truncate(len);
+        self.out
+    }
+
+    /// Returns the text as it is.
+    fn into_string(self) -> String {
+        self.out
+    }
+}
+
+/// An entry that couldn't be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    kind: ErrorKind,
+}
+
+impl ParseError {
+    /// The line the entry started on, starting at `1`.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// What went wrong.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// The kinds of [`ParseError`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// There's no `=` or `:` after the key.
+    MissingSeparator,
+    /// The key is empty.
+    EmptyKey,
+    /// A quoted value has no closing quote.
+    UnterminatedQuote,
+    /// Something other than whitespace or a comment follows a quoted value.
+    TrailingCharacters,
+    /// A `\u` escape isn't followed by four hex digits of a valid `char`.
+    InvalidEscape,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::MissingSeparator => "expected `=` or `:` after the key",
+            Self::EmptyKey => "the key is empty",
+            Self::UnterminatedQuote => "the quoted value has no closing quote",
+            Self::TrailingCharacters => "unexpected characters after the quoted value",
+            Self::InvalidEscape => "invalid `\\u` escape",
+        })
+    }
+}
+
+/// Writes the pairs as `key=value` lines, escaping them so they read back the
+/// same with [`parse`]. The result implements [`fmt::Display`], so nothing is
+/// written until it's formatted.
+///
+/// The iterator is cloned to check the keys, and each time the result is
+/// formatted.
+///
+/// # Errors
+///
+/// Returns an [`EncodeError`] for the first pair with an empty key, since
+/// [`parse`] rejects empty keys. Nothing has been written at that point.
+pub fn encode<I, K, V>(pairs: I) -> Result<Encoded<I::IntoIter>, EncodeError>
+where
+    I: IntoIterator,
+    I::IntoIter: Clone,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let iter = pairs.into_iter();
+
+    let empty = iter
+        .clone()
+        .position(|item| item.with_key(|key| key.as_ref().is_empty()));
+    match empty {
+        Some(index) => Err(EncodeError { index }),
+        None => Ok(Encoded { iter }),
+    }
+}
+
+/// Pairs written as `key=value` lines.
+///
+/// See: [`encode`]
+#[must_use = "this does nothing unless formatted"]
+#[derive(Debug, Clone)]
+pub struct Encoded<I> {
+    iter: I,
+}
+
+impl<I, K, V> fmt::Display for Encoded<I>
+where
+    I: Iterator + Clone,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (k, v) in self.iter.clone().map(KeyValue::into_parts) {
+            write_escaped(f, k.as_ref(), true)?;
+            f.write_char('=')?;
+            write_escaped(f, v.as_ref(), false)?;
+            f.write_char('\n')?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A pair that couldn't be encoded, because its key is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncodeError {
+    index: usize,
+}
+
+impl EncodeError {
+    /// The position of the pair with the empty key, starting at `0`.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "pair {}: the key is empty", self.index)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EncodeError {}
+
+fn write_escaped(f: &mut fmt::Formatter<'_>, s: &str, key: bool) -> fmt::Result {
+    let content = s.trim_matches(WHITESPACE);
+    let leading = s.len() - s.trim_start_matches(WHITESPACE).len();
+    let trailing_start = leading + content.len();
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            '\x0C' => f.write_str("\\u000C")?,
+            '#' => f.write_str("\\#")?,
+            ' ' if key || i < leading || i >= trailing_start => f.write_str("\\ ")?,
+            '=' | ':' | '!' if key => write!(f, "\\{c}")?,
+            '"' | '\'' if !key && i == 0 => write!(f, "\\{c}")?,
+            c => f.write_char(c)?,
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+
+    use pretty_assertions::assert_eq;
+
+    use super::{encode, parse, EncodeError, ErrorKind};
+
+    fn parse_ok(text: &str) -> Vec<(Cow<'_, str>, Cow<'_, str>)> {
+        parse(text).collect::<Result<_, _>>().unwrap()
+    }
+
+    #[test]
+    fn basic() {
+        let text = "\
+# comment
+! also a comment
+
+a=1
+  b : 2
+c=
+export d = four
+export=5
+color=#fff
+e = x # trailing comment
+";
+
+        assert_eq!(
+            vec![
+                ("a".into(), "1".into()),
+                ("b".into(), "2".into()),
+                ("c".into(), "".into()),
+                ("d".into(), "four".into()),
+                ("export".into(), "5".into()),
+                ("color".into(), "#fff".into()),
+                ("e".into(), "x".into()),
+            ],
+            parse_ok(text)
+        );
+
+        assert!(parse_ok(text)
+            .iter()
+            .all(|(k, v)| matches!((k, v), (Cow::Borrowed(_), Cow::Borrowed(_)))));
+    }
+
+    #[test]
+    fn quoting_and_escapes() {
+        let text = r#"
+double = "a \"quoted\" \\ value\n" # comment
+single = 'no \escapes here'
+multi = "line one
+line two"
+escaped = \ padded\ 
+unicode = café
+key\ with\=stuff = v
+continued = one \
+            two \
+            three
+"#;
+
+        assert_eq!(
+            vec![
+                ("double".into(), "a \"quoted\" \\ value\n".into()),
+                ("single".into(), r"no \escapes here".into()),
+                ("multi".into(), "line one\nline two".into()),
+                ("escaped".into(), " padded ".into()),
+                ("unicode".into(), "café".into()),
+                ("key with=stuff".into(), "v".into()),
+                ("continued".into(), "one two three".into()),
+            ],
+            parse_ok(text)
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let text = "\
+ok=1
+no separator
+=empty
+quote=\"open
+";
+
+        let results: Vec<_> = parse(text)
+            .map(|r| r.map_err(|e| (e.line(), e.kind())))
+            .collect();
+
+        assert_eq!(
+            vec![
+                Ok(("ok".into(), "1".into())),
+                Err((2, ErrorKind::MissingSeparator)),
+                Err((3, ErrorKind::EmptyKey)),
+                Err((4, ErrorKind::UnterminatedQuote)),
+            ],
+            results
+        );
+
+        let err = parse("a = 'x' y").next().unwrap().unwrap_err();
+        assert_eq!(ErrorKind::TrailingCharacters, err.kind());
+        assert_eq!(
+            "line 1: unexpected characters after the quoted value",
+            err.to_string()
+        );
+
+        let err = parse("\n\na = \\u12").next().unwrap().unwrap_err();
+        assert_eq!((3, ErrorKind::InvalidEscape), (err.line(), err.kind()));
+
+        // `from_str_radix` alone would take the sign.
+        let err = parse("a=\\u+041").next().unwrap().unwrap_err();
+        assert_eq!(ErrorKind::InvalidEscape, err.kind());
+    }
+
+    #[test]
+    fn round_trip() {
+        let pairs = vec![
+            ("plain", "value"),
+            ("spaces in key", "  padded  "),
+            ("key=with:separators!", "#not a comment"),
+            ("#starts with hash", "\"quoted\""),
+            ("export", "'single'"),
+            ("export thing", "back\\slash"),
+            ("multi\nline", "a\r\nb\tc"),
+            ("empty", ""),
+            ("unicode", "naïve ✓"),
+            ("a\u{A0}", "\u{3000}x\u{A0}"),
+            ("k", "\u{B}v\u{C}"),
+            ("form\u{C}feed", "\u{C}"),
+        ];
+
+        let text = encode(pairs.iter()).unwrap().to_string();
+        let parsed = parse_ok(&text);
+
+        assert_eq!(
+            pairs,
+            parsed
+                .iter()
+                .map(|(k, v)| (k.as_ref(), v.as_ref()))
+                .collect::<Vec<_>>(),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn empty_key() {
+        // Written as `=x`, it would read back as an error.
+        assert_eq!(
+            ErrorKind::EmptyKey,
+            parse("=x").next().unwrap().unwrap_err().kind()
+        );
+
+        let err = encode([("a", "1"), ("", "x"), ("b", "2")]).unwrap_err();
+        assert_eq!(EncodeError { index: 1 }, err);
+        assert_eq!("pair 1: the key is empty", err.to_string());
+    }
+}
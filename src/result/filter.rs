@@ -0,0 +1,268 @@
+use core::{
+    fmt,
+    iter::{DoubleEndedIterator, FusedIterator, Iterator},
+};
+
+/// Filters `Ok` items based on the keys, the first element of a two-element
+/// tuple (like `(K, V)`). `Err` items are always passed through.
+///
+/// See: [`TryIterMap::filter_keys`]
+///
+/// [`TryIterMap::filter_keys`]: crate::TryIterMap::filter_keys
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct TryFilterKeys<I, P> {
+    iter: I,
+    predicate: P,
+}
+
+impl<I, P> TryFilterKeys<I, P> {
+    pub(crate) fn new<K, V, E>(iter: I, predicate: P) -> Self
+    where
+        I: Iterator<Item = Result<(K, V), E>>,
+        P: FnMut(&K) -> bool,
+    {
+        Self { iter, predicate }
+    }
+
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items in this adaptor.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the adaptor, returning the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I, P> fmt::Debug for TryFilterKeys<I, P>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TryFilterKeys")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I, P, K, V, E> Iterator for TryFilterKeys<I, P>
+where
+    I: Iterator<Item = Result<(K, V), E>>,
+    P: FnMut(&K) -> bool,
+{
+    type Item = Result<(K, V), E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let predicate = &mut self.predicate;
+
+        self.iter
+            .by_ref()
+            .find(|item| item.as_ref().map_or(true, |(k, _)| predicate(k)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut predicate = self.predicate;
+        self.iter.fold(init, move |acc, item| {
+            if item.as_ref().map_or(true, |(k, _)| predicate(k)) {
+                f(acc, item)
+            } else {
+                acc
+            }
+        })
+    }
+
+    fn count(self) -> usize {
+        let mut predicate = self.predicate;
+        self.iter
+            .map(|item| usize::from(item.as_ref().map_or(true, |(k, _)| predicate(k))))
+            .sum()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.fold(None, |_, item| Some(item))
+    }
+}
+
+impl<I, P, K, V, E> DoubleEndedIterator for TryFilterKeys<I, P>
+where
+    I: DoubleEndedIterator<Item = Result<(K, V), E>>,
+    P: FnMut(&K) -> bool,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let predicate = &mut self.predicate;
+
+        self.iter
+            .by_ref()
+            .rev()
+            .find(|item| item.as_ref().map_or(true, |(k, _)| predicate(k)))
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut predicate = self.predicate;
+        self.iter.rfold(init, move |acc, item| {
+            if item.as_ref().map_or(true, |(k, _)| predicate(k)) {
+                f(acc, item)
+            } else {
+                acc
+            }
+        })
+    }
+}
+
+impl<I, P, K, V, E> FusedIterator for TryFilterKeys<I, P>
+where
+    I: FusedIterator<Item = Result<(K, V), E>>,
+    P: FnMut(&K) -> bool,
+{
+}
+
+/// Filters `Ok` items based on the values, the second element of a
+/// two-element tuple (like `(K, V)`). `Err` items are always passed through.
+///
+/// See: [`TryIterMap::filter_values`]
+///
+/// [`TryIterMap::filter_values`]: crate::TryIterMap::filter_values
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct TryFilterValues<I, P> {
+    iter: I,
+    predicate: P,
+}
+
+impl<I, P> TryFilterValues<I, P> {
+    pub(crate) fn new<K, V, E>(iter: I, predicate: P) -> Self
+    where
+        I: Iterator<Item = Result<(K, V), E>>,
+        P: FnMut(&V) -> bool,
+    {
+        Self { iter, predicate }
+    }
+
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items in this adaptor.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the adaptor, returning the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I, P> fmt::Debug for TryFilterValues<I, P>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TryFilterValues")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I, P, K, V, E> Iterator for TryFilterValues<I, P>
+where
+    I: Iterator<Item = Result<(K, V), E>>,
+    P: FnMut(&V) -> bool,
+{
+    type Item = Result<(K, V), E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let predicate = &mut self.predicate;
+
+        self.iter
+            .by_ref()
+            .find(|item| item.as_ref().map_or(true, |(_, v)| predicate(v)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut predicate = self.predicate;
+        self.iter.fold(init, move |acc, item| {
+            if item.as_ref().map_or(true, |(_, v)| predicate(v)) {
+                f(acc, item)
+            } else {
+                acc
+            }
+        })
+    }
+
+    fn count(self) -> usize {
+        let mut predicate = self.predicate;
+        self.iter
+            .map(|item| usize::from(item.as_ref().map_or(true, |(_, v)| predicate(v))))
+            .sum()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.fold(None, |_, item| Some(item))
+    }
+}
+
+impl<I, P, K, V, E> DoubleEndedIterator for TryFilterValues<I, P>
+where
+    I: DoubleEndedIterator<Item = Result<(K, V), E>>,
+    P: FnMut(&V) -> bool,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let predicate = &mut self.predicate;
+
+        self.iter
+            .by_ref()
+            .rev()
+            .find(|item| item.as_ref().map_or(true, |(_, v)| predicate(v)))
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut predicate = self.predicate;
+        self.iter.rfold(init, move |acc, item| {
+            if item.as_ref().map_or(true, |(_, v)| predicate(v)) {
+                f(acc, item)
+            } else {
+                acc
+            }
+        })
+    }
+}
+
+impl<I, P, K, V, E> FusedIterator for TryFilterValues<I, P>
+where
+    I: FusedIterator<Item = Result<(K, V), E>>,
+    P: FnMut(&V) -> bool,
+{
+}
@@ -0,0 +1,280 @@
+use core::{
+    fmt,
+    iter::{DoubleEndedIterator, FusedIterator, Iterator},
+};
+
+/// Maps keys, the first element of a two-element tuple (like `(K, V)`), inside
+/// `Ok` items, passing `Err` items through untouched.
+///
+/// For use, see the documentation on [`TryIterMap::map_keys`].
+///
+/// [`TryIterMap::map_keys`]: crate::TryIterMap::map_keys
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct TryMapKeys<I, P> {
+    iter: I,
+    predicate: P,
+}
+
+impl<I, P> TryMapKeys<I, P> {
+    pub(crate) fn new<K, V, E, L>(iter: I, predicate: P) -> Self
+    where
+        I: Iterator<Item = Result<(K, V), E>>,
+        P: FnMut(K) -> L,
+    {
+        Self { iter, predicate }
+    }
+
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items in this adaptor.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the adaptor, returning the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I, P> fmt::Debug for TryMapKeys<I, P>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TryMapKeys")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I, P, K, L, V, E> Iterator for TryMapKeys<I, P>
+where
+    I: Iterator<Item = Result<(K, V), E>>,
+    P: FnMut(K) -> L,
+{
+    type Item = Result<(L, V), E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|item| item.map(|(k, v)| ((self.predicate)(k), v)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut predicate = self.predicate;
+        self.iter.fold(init, move |acc, item| {
+            f(acc, item.map(|(k, v)| (predicate(k), v)))
+        })
+    }
+
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter
+            .nth(n)
+            .map(|item| item.map(|(k, v)| ((self.predicate)(k), v)))
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        let mut predicate = self.predicate;
+        self.iter
+            .last()
+            .map(|item| item.map(|(k, v)| (predicate(k), v)))
+    }
+}
+
+impl<I, P, K, L, V, E> DoubleEndedIterator for TryMapKeys<I, P>
+where
+    I: DoubleEndedIterator<Item = Result<(K, V), E>>,
+    P: FnMut(K) -> L,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next_back()
+            .map(|item| item.map(|(k, v)| ((self.predicate)(k), v)))
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut predicate = self.predicate;
+        self.iter.rfold(init, move |acc, item| {
+            f(acc, item.map(|(k, v)| (predicate(k), v)))
+        })
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter
+            .nth_back(n)
+            .map(|item| item.map(|(k, v)| ((self.predicate)(k), v)))
+    }
+}
+
+impl<I, P, K, L, V, E> FusedIterator for TryMapKeys<I, P>
+where
+    I: FusedIterator<Item = Result<(K, V), E>>,
+    P: FnMut(K) -> L,
+{
+}
+
+impl<I, P, K, L, V, E> ExactSizeIterator for TryMapKeys<I, P>
+where
+    I: ExactSizeIterator<Item = Result<(K, V), E>>,
+    P: FnMut(K) -> L,
+{
+}
+
+/// Maps values, the second element of a two-element tuple (like `(K, V)`),
+/// inside `Ok` items, passing `Err` items through untouched.
+///
+/// For use, see the documentation on [`TryIterMap::map_values`].
+///
+/// [`TryIterMap::map_values`]: crate::TryIterMap::map_values
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct TryMapValues<I, P> {
+    iter: I,
+    predicate: P,
+}
+
+impl<I, P> TryMapValues<I, P> {
+    pub(crate) fn new<K, V, E, W>(iter: I, predicate: P) -> Self
+    where
+        I: Iterator<Item = Result<(K, V), E>>,
+        P: FnMut(V) -> W,
+    {
+        Self { iter, predicate }
+    }
+
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items in this adaptor.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the adaptor, returning the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I, P> fmt::Debug for TryMapValues<I, P>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TryMapValues")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I, P, K, V, W, E> Iterator for TryMapValues<I, P>
+where
+    I: Iterator<Item = Result<(K, V), E>>,
+    P: FnMut(V) -> W,
+{
+    type Item = Result<(K, W), E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|item| item.map(|(k, v)| (k, (self.predicate)(v))))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut predicate = self.predicate;
+        self.iter.fold(init, move |acc, item| {
+            f(acc, item.map(|(k, v)| (k, predicate(v))))
+        })
+    }
+
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter
+            .nth(n)
+            .map(|item| item.map(|(k, v)| (k, (self.predicate)(v))))
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        let mut predicate = self.predicate;
+        self.iter
+            .last()
+            .map(|item| item.map(|(k, v)| (k, predicate(v))))
+    }
+}
+
+impl<I, P, K, V, W, E> DoubleEndedIterator for TryMapValues<I, P>
+where
+    I: DoubleEndedIterator<Item = Result<(K, V), E>>,
+    P: FnMut(V) -> W,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next_back()
+            .map(|item| item.map(|(k, v)| (k, (self.predicate)(v))))
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut predicate = self.predicate;
+        self.iter.rfold(init, move |acc, item| {
+            f(acc, item.map(|(k, v)| (k, predicate(v))))
+        })
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter
+            .nth_back(n)
+            .map(|item| item.map(|(k, v)| (k, (self.predicate)(v))))
+    }
+}
+
+impl<I, P, K, V, W, E> FusedIterator for TryMapValues<I, P>
+where
+    I: FusedIterator<Item = Result<(K, V), E>>,
+    P: FnMut(V) -> W,
+{
+}
+
+impl<I, P, K, V, W, E> ExactSizeIterator for TryMapValues<I, P>
+where
+    I: ExactSizeIterator<Item = Result<(K, V), E>>,
+    P: FnMut(V) -> W,
+{
+}
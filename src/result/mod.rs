@@ -0,0 +1,311 @@
+mod filter;
+mod map;
+mod swap;
+
+pub use filter::{TryFilterKeys, TryFilterValues};
+pub use map::{TryMapKeys, TryMapValues};
+pub use swap::TrySwap;
+
+use crate::KeyValue;
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl<T, E> Sealed for Result<T, E> {}
+}
+
+/// Implemented for [`Result`], so methods like
+/// [`IterMap::partition_result_values`] can work with its `Ok` and `Err`
+/// types.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+///
+/// [`IterMap::partition_result_values`]: crate::IterMap::partition_result_values
+pub trait ResultValue: sealed::Sealed {
+    /// The `Ok` type.
+    type Ok;
+    /// The `Err` type.
+    type Err;
+
+    /// Converts to a [`Result`].
+    fn into_result(self) -> Result<Self::Ok, Self::Err>;
+}
+
+impl<T, E> ResultValue for Result<T, E> {
+    type Ok = T;
+    type Err = E;
+
+    fn into_result(self) -> Result<T, E> {
+        self
+    }
+}
+
+/// Adds [`IterMap`]-like methods for `Iterator`s over `Result<(K, V), E>`,
+/// like those reading pairs from files or sockets. The methods work on the
+/// `(K, V)` inside `Ok` items and pass `Err` items through untouched, so
+/// pipelines stay lazy without unwrapping every item first.
+///
+/// # Example
+///
+/// ```
+/// # use std::collections::HashMap;
+/// #
+/// use itermap::TryIterMap;
+/// # use pretty_assertions::assert_eq;
+///
+/// fn read() -> impl Iterator<Item = Result<(&'static str, &'static str), String>> {
+///     vec![
+///         Ok(("a", "1")),
+///         Err(String::from("connection reset")),
+///         Ok(("b", "2")),
+///     ]
+///     .into_iter()
+/// }
+///
+/// let items: Vec<_> = read()
+///     .map_keys(str::to_uppercase)
+///     .filter_values(|v| v != &"2")
+///     .collect();
+///
+/// assert_eq!(
+///     vec![
+///         Ok((String::from("A"), "1")),
+///         Err(String::from("connection reset")),
+///     ],
+///     items,
+/// );
+///
+/// // Or stop at the first error.
+/// let map: Result<HashMap<_, _>, _> = read().swap().collect();
+/// assert_eq!(Err(String::from("connection reset")), map);
+/// ```
+///
+/// [`IterMap`]: crate::IterMap
+pub trait TryIterMap<K, V, E>: Iterator<Item = Result<(K, V), E>> + Sized {
+    /// Maps keys, the first element of `Ok((K, V))` items, leaving the other
+    /// element and `Err` items untouched.
+    ///
+    /// See [`IterMap::map_keys`](crate::IterMap::map_keys).
+    fn map_keys<Fk, L>(self, key_op: Fk) -> TryMapKeys<Self, Fk>
+    where
+        Fk: FnMut(K) -> L,
+    {
+        TryMapKeys::new(self, key_op)
+    }
+
+    /// Maps values, the second element of `Ok((K, V))` items, leaving the
+    /// other element and `Err` items untouched.
+    ///
+    /// See [`IterMap::map_values`](crate::IterMap::map_values).
+    fn map_values<Fv, W>(self, value_op: Fv) -> TryMapValues<Self, Fv>
+    where
+        Fv: FnMut(V) -> W,
+    {
+        TryMapValues::new(self, value_op)
+    }
+
+    /// Filters `Ok((K, V))` items based on their keys. `Err` items are always
+    /// kept.
+    ///
+    /// See [`IterMap::filter_keys`](crate::IterMap::filter_keys).
+    fn filter_keys<Fk>(self, key_op: Fk) -> TryFilterKeys<Self, Fk>
+    where
+        Fk: FnMut(&K) -> bool,
+    {
+        TryFilterKeys::new(self, key_op)
+    }
+
+    /// Filters `Ok((K, V))` items based on their values. `Err` items are
+    /// always kept.
+    ///
+    /// See [`IterMap::filter_values`](crate::IterMap::filter_values).
+    fn filter_values<Fv>(self, value_op: Fv) -> TryFilterValues<Self, Fv>
+    where
+        Fv: FnMut(&V) -> bool,
+    {
+        TryFilterValues::new(self, value_op)
+    }
+
+    /// Swaps `Ok((K, V))` items so they're `Ok((V, K))`, leaving `Err` items
+    /// untouched.
+    ///
+    /// See [`IterMap::swap`](crate::IterMap::swap).
+    fn swap(self) -> TrySwap<Self> {
+        TrySwap::new(self)
+    }
+}
+
+impl<I, K, V, E> TryIterMap<K, V, E> for I where I: Iterator<Item = Result<(K, V), E>> {}
+
+pub(crate) fn partition<I, K, V, A, B>(iter: I) -> (A, B)
+where
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    V: ResultValue,
+    A: Default + Extend<(K, V::Ok)>,
+    B: Default + Extend<(K, V::Err)>,
+{
+    let mut oks = A::default();
+    let mut errs = B::default();
+
+    for (k, v) in iter.map(KeyValue::into_parts) {
+        match v.into_result() {
+            Ok(v) => oks.extend(Some((k, v))),
+            Err(e) => errs.extend(Some((k, e))),
+        }
+    }
+
+    (oks, errs)
+}
+
+pub(crate) fn collect_all_or_errors<I, K, V, A, B>(iter: I) -> Result<A, B>
+where
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    V: ResultValue,
+    A: Default + Extend<(K, V::Ok)>,
+    B: Default + Extend<(K, V::Err)>,
+{
+    let mut result = Ok(A::default());
+
+    for (k, v) in iter.map(KeyValue::into_parts) {
+        match (&mut result, v.into_result()) {
+            (Ok(oks), Ok(v)) => oks.extend(Some((k, v))),
+            // Once there's an error, the values are no longer needed.
+            (Ok(_), Err(e)) => {
+                let mut errs = B::default();
+                errs.extend(Some((k, e)));
+                result = Err(errs);
+            }
+            (Err(errs), Err(e)) => errs.extend(Some((k, e))),
+            (Err(_), Ok(_)) => {}
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, HashMap};
+
+    use pretty_assertions::assert_eq;
+
+    use crate::IterMap;
+
+    fn parsed() -> Vec<(&'static str, Result<u8, String>)> {
+        vec![("a", "1"), ("b", "x"), ("c", "3"), ("d", "300")]
+            .into_iter()
+            .map_values(|v| v.parse::<u8>().map_err(|e| e.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn partition() {
+        let (oks, errs): (BTreeMap<_, _>, Vec<_>) = parsed().into_iter().partition_result_values();
+
+        assert_eq!(BTreeMap::from([("a", 1), ("c", 3)]), oks);
+        assert_eq!(
+            vec![
+                ("b", String::from("invalid digit found in string")),
+                ("d", String::from("number too large to fit in target type")),
+            ],
+            errs
+        );
+    }
+
+    #[test]
+    fn collect_all() {
+        let all: Result<HashMap<_, _>, Vec<_>> = parsed()
+            .into_iter()
+            .filter_keys(|k| k != &"b" && k != &"d")
+            .collect_all_or_errors();
+
+        assert_eq!(Ok(HashMap::from([("a", 1), ("c", 3)])), all);
+    }
+
+    #[test]
+    fn collect_errors() {
+        let all: Result<HashMap<_, _>, Vec<_>> = parsed().into_iter().collect_all_or_errors();
+
+        assert_eq!(
+            Err(vec![
+                ("b", String::from("invalid digit found in string")),
+                ("d", String::from("number too large to fit in target type")),
+            ]),
+            all
+        );
+    }
+
+    #[test]
+    fn try_iter_map() {
+        use crate::TryIterMap;
+
+        let items = vec![Ok(("a", 1)), Err("bad"), Ok(("b", 2)), Ok(("c", 3))];
+
+        let mapped: Vec<_> = items
+            .clone()
+            .into_iter()
+            .map_keys(str::to_uppercase)
+            .map_values(|v| v * 10)
+            .collect();
+        assert_eq!(
+            vec![
+                Ok((String::from("A"), 10)),
+                Err("bad"),
+                Ok((String::from("B"), 20)),
+                Ok((String::from("C"), 30)),
+            ],
+            mapped
+        );
+
+        let filtered: Vec<_> = items
+            .clone()
+            .into_iter()
+            .filter_keys(|k| k != &"a")
+            .filter_values(|v| v != &3)
+            .collect();
+        assert_eq!(vec![Err("bad"), Ok(("b", 2))], filtered);
+
+        let swapped: Vec<_> = items.clone().into_iter().swap().rev().collect();
+        assert_eq!(
+            vec![Ok((3, "c")), Ok((2, "b")), Err("bad"), Ok((1, "a"))],
+            swapped
+        );
+
+        // If this doesn't compile, the adaptors don't implement `ExactSizeIterator`
+        assert_eq!(4, items.iter().cloned().map_keys(|k| k).swap().len());
+    }
+
+    #[test]
+    fn try_internal_iteration() {
+        use crate::TryIterMap;
+
+        let items = vec![Ok(("a", 1)), Err("bad"), Ok(("b", 2)), Ok(("c", 3))];
+        let iter = || items.clone().into_iter();
+
+        assert_eq!(4, iter().map_values(|v| v * 10).count());
+        assert_eq!(Some(Ok(("c", 30))), iter().map_values(|v| v * 10).last());
+        assert_eq!(Some(Err("bad")), iter().map_keys(str::len).nth(1));
+        assert_eq!(Some(Ok((2, "b"))), iter().swap().nth_back(1));
+        assert_eq!(3, iter().filter_values(|v| v != &2).count());
+        assert_eq!(Some(Ok(("b", 2))), iter().filter_keys(|k| k != &"c").last());
+        assert_eq!(
+            8,
+            iter()
+                .map_values(|v| v * 2)
+                .filter_keys(|k| k != &"b")
+                .fold(0, |acc, item| acc + item.map_or(0, |(_, v)| v))
+        );
+        assert_eq!(
+            vec![Ok((3, "c")), Ok((2, "b")), Err("bad")],
+            iter()
+                .swap()
+                .filter_keys(|k| k != &1)
+                .rfold(Vec::new(), |mut acc, item| {
+                    acc.push(item);
+                    acc
+                })
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use core::{fmt, iter::FusedIterator};
+
+/// Swaps the positions of `Ok` items like `(K, V)` so they're `(V, K)`,
+/// passing `Err` items through untouched.
+///
+/// See: [`TryIterMap::swap`]
+///
+/// [`TryIterMap::swap`]: crate::TryIterMap::swap
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct TrySwap<I> {
+    iter: I,
+}
+
+impl<I> TrySwap<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        Self { iter }
+    }
+
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items in this adaptor.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the adaptor, returning the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+
+    fn map_item<K, V, E>(item: Result<(K, V), E>) -> Result<(V, K), E> {
+        item.map(|(k, v)| (v, k))
+    }
+}
+
+impl<I, K, V, E> Iterator for TrySwap<I>
+where
+    I: Iterator<Item = Result<(K, V), E>>,
+{
+    type Item = Result<(V, K), E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(Self::map_item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter
+            .fold(init, move |acc, item| f(acc, Self::map_item(item)))
+    }
+
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n).map(Self::map_item)
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.iter.last().map(Self::map_item)
+    }
+}
+
+impl<I, K, V, E> DoubleEndedIterator for TrySwap<I>
+where
+    I: DoubleEndedIterator<Item = Result<(K, V), E>>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(Self::map_item)
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter
+            .rfold(init, move |acc, item| f(acc, Self::map_item(item)))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth_back(n).map(Self::map_item)
+    }
+}
+
+impl<I, K, V, E> ExactSizeIterator for TrySwap<I> where
+    I: ExactSizeIterator<Item = Result<(K, V), E>>
+{
+}
+impl<I, K, V, E> FusedIterator for TrySwap<I> where I: FusedIterator<Item = Result<(K, V), E>> {}
+
+impl<I> fmt::Debug for TrySwap<I>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrySwap").field("iter", &self.iter).finish()
+    }
+}
+
+impl<I> Clone for TrySwap<I>
+where
+    I: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<I> Copy for TrySwap<I> where I: Copy {}
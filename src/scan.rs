@@ -0,0 +1,228 @@
+use core::{
+    fmt,
+    hash::Hash,
+    iter::{FusedIterator, Iterator},
+    ops::Sub,
+};
+use std::collections::HashMap;
+
+use crate::KeyValue;
+
+/// An `Iterator` that keeps running state for each key, yielding `(K, W)`
+/// for each item.
+///
+/// See: [`IterMap::scan_by_key`]
+///
+/// [`IterMap::scan_by_key`]: crate::IterMap::scan_by_key
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct ScanByKey<I, K, S, F> {
+    iter: I,
+    init: S,
+    states: HashMap<K, S>,
+    f: F,
+}
+
+/// [`ScanByKey`] yielding the difference from the previous value for the same
+/// key, or `None` for the first. Returned by [`IterMap::deltas_by_key`].
+///
+/// [`IterMap::deltas_by_key`]: crate::IterMap::deltas_by_key
+pub type DeltasByKey<I, K, V> =
+    ScanByKey<I, K, Option<V>, fn(&mut Option<V>, V) -> Option<<V as Sub>::Output>>;
+
+impl<I, K, S, F> ScanByKey<I, K, S, F> {
+    pub(crate) fn new(iter: I, init: S, f: F) -> Self {
+        Self {
+            iter,
+            init,
+            states: HashMap::new(),
+            f,
+        }
+    }
+
+    /// Returns the state for each key seen so far.
+    pub fn states(&self) -> &HashMap<K, S> {
+        &self.states
+    }
+
+    /// Consumes the adaptor, returning the state for each key seen.
+    pub fn into_states(self) -> HashMap<K, S> {
+        self.states
+    }
+
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items in this adaptor.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the adaptor, returning the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I, K, S, F> fmt::Debug for ScanByKey<I, K, S, F>
+where
+    I: fmt::Debug,
+    K: fmt::Debug,
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScanByKey")
+            .field("iter", &self.iter)
+            .field("init", &self.init)
+            .field("states", &self.states)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I, K, V, S, F, W> Iterator for ScanByKey<I, K, S, F>
+where
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: Eq + Hash + Clone,
+    S: Clone,
+    F: FnMut(&mut S, V) -> W,
+{
+    type Item = (K, W);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.iter.next()?.into_parts();
+        let state = match self.states.get_mut(&key) {
+            Some(state) => state,
+            None => self
+                .states
+                .entry(key.clone())
+                .or_insert_with(|| self.init.clone()),
+        };
+
+        let output = (self.f)(state, value);
+        Some((key, output))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, K, V, S, F, W> FusedIterator for ScanByKey<I, K, S, F>
+where
+    I: FusedIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: Eq + Hash + Clone,
+    S: Clone,
+    F: FnMut(&mut S, V) -> W,
+{
+}
+
+impl<I, K, V, S, F, W> ExactSizeIterator for ScanByKey<I, K, S, F>
+where
+    I: ExactSizeIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: Eq + Hash + Clone,
+    S: Clone,
+    F: FnMut(&mut S, V) -> W,
+{
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+/// The step function for [`DeltasByKey`].
+pub(crate) fn delta<V>(previous: &mut Option<V>, value: V) -> Option<V::Output>
+where
+    V: Sub + Clone,
+{
+    previous
+        .replace(value.clone())
+        .map(|previous| value - previous)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::IterMap;
+
+    fn transactions() -> Vec<(&'static str, i64)> {
+        vec![
+            ("alice", 100),
+            ("bob", 20),
+            ("alice", -30),
+            ("bob", 5),
+            ("alice", 10),
+        ]
+    }
+
+    #[test]
+    fn running_totals() {
+        let mut totals = transactions().into_iter().scan_by_key(0, |total, amount| {
+            *total += amount;
+            *total
+        });
+
+        let running: Vec<_> = totals.by_ref().collect();
+        assert_eq!(
+            vec![
+                ("alice", 100),
+                ("bob", 20),
+                ("alice", 70),
+                ("bob", 25),
+                ("alice", 80)
+            ],
+            running
+        );
+        assert_eq!(
+            HashMap::from([("alice", 80), ("bob", 25)]),
+            totals.into_states()
+        );
+    }
+
+    #[test]
+    fn counts() {
+        let numbered: Vec<_> = vec![('a', "x"), ('b', "y"), ('a', "z")]
+            .into_iter()
+            .scan_by_key(0, |n, v| {
+                *n += 1;
+                (*n, v)
+            })
+            .collect();
+
+        assert_eq!(
+            vec![('a', (1, "x")), ('b', (1, "y")), ('a', (2, "z"))],
+            numbered
+        );
+    }
+
+    #[test]
+    fn deltas() {
+        let readings = vec![("meter", 10), ("gauge", 3), ("meter", 15), ("meter", 12)];
+
+        let deltas: Vec<_> = readings.into_iter().deltas_by_key().collect();
+
+        assert_eq!(
+            vec![
+                ("meter", None),
+                ("gauge", None),
+                ("meter", Some(5)),
+                ("meter", Some(-3))
+            ],
+            deltas
+        );
+    }
+
+    #[test]
+    fn exact_size() {
+        let iter = transactions().into_iter().deltas_by_key();
+        assert_eq!(5, iter.len());
+    }
+}
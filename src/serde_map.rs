@@ -0,0 +1,325 @@
+use core::{cell::RefCell, fmt, marker::PhantomData};
+
+use serde::{
+    de::{DeserializeSeed, MapAccess, Visitor},
+    ser::{Error as _, SerializeMap},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::KeyValue;
+
+/// Serializes an `Iterator` over two-element tuples (like `(K, V)`) as a map,
+/// without collecting it into a map first.
+///
+/// The iterator is cloned each time this is serialized. For iterators that
+/// can't be cloned, see [`SerializeAsMapOnce`].
+///
+/// # Example
+///
+/// ```
+/// # use std::collections::HashMap;
+/// #
+/// use itermap::{IterMap, SerializeAsMap};
+/// # use pretty_assertions::assert_eq;
+///
+/// let map = HashMap::from([("a", 1)]);
+///
+/// let json = serde_json::to_string(&SerializeAsMap::new(map.iter().swap())).unwrap();
+///
+/// assert_eq!(r#"{"1":"a"}"#, json);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SerializeAsMap<I>(I);
+
+impl<I> SerializeAsMap<I> {
+    /// Wraps the iterator.
+    pub fn new(iter: I) -> Self {
+        Self(iter)
+    }
+
+    /// Returns the wrapped iterator.
+    pub fn into_inner(self) -> I {
+        self.0
+    }
+}
+
+impl<I, K, V> Serialize for SerializeAsMap<I>
+where
+    I: Iterator + Clone,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: Serialize,
+    V: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_map(self.0.clone(), serializer)
+    }
+}
+
+/// Like [`SerializeAsMap`], but for iterators that can only be iterated over
+/// once. The iterator is held in a [`RefCell`], and is consumed by the first
+/// serialization. Serializing it again is an error.
+///
+/// # Example
+///
+/// ```
+/// use itermap::{IterMap, SerializeAsMapOnce};
+/// # use pretty_assertions::assert_eq;
+///
+/// let items = vec![(String::from("a"), 1), (String::from("b"), 2)];
+///
+/// let once = SerializeAsMapOnce::new(items.into_iter().map_values(|v| v * 10));
+///
+/// assert_eq!(r#"{"a":10,"b":20}"#, serde_json::to_string(&once).unwrap());
+/// assert!(serde_json::to_string(&once).is_err());
+/// ```
+pub struct SerializeAsMapOnce<I>(RefCell<Option<I>>);
+
+impl<I> SerializeAsMapOnce<I> {
+    /// Wraps the iterator.
+    pub fn new(iter: I) -> Self {
+        Self(RefCell::new(Some(iter)))
+    }
+
+    /// Returns the wrapped iterator, if it hasn't been serialized yet.
+    pub fn into_inner(self) -> Option<I> {
+        self.0.into_inner()
+    }
+}
+
+impl<I, K, V> Serialize for SerializeAsMapOnce<I>
+where
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: Serialize,
+    V: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let iter = self
+            .0
+            .borrow_mut()
+            .take()
+            .ok_or_else(|| S::Error::custom("iterator has already been serialized"))?;
+
+        serialize_map(iter, serializer)
+    }
+}
+
+impl<I> fmt::Debug for SerializeAsMapOnce<I>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SerializeAsMapOnce").field(&self.0).finish()
+    }
+}
+
+fn serialize_map<I, K, V, S>(iter: I, serializer: S) -> Result<S::Ok, S::Error>
+where
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: Serialize,
+    V: Serialize,
+    S: Serializer,
+{
+    let len = match iter.size_hint() {
+        (lower, Some(upper)) if lower == upper => Some(lower),
+        _ => None,
+    };
+
+    let mut map = serializer.serialize_map(len)?;
+    for (k, v) in iter.map(KeyValue::into_parts) {
+        map.serialize_entry(&k, &v)?;
+    }
+
+    map.end()
+}
+
+/// A [`DeserializeSeed`] that deserializes a map and passes each entry to a
+/// closure as it's read, without building a map.
+///
+/// See [`deserialize_map_with`] and [`deserialize_map_into`] for the common
+/// cases. Use this directly when deserializing a map nested in something else.
+pub struct MapEntries<K, V, F> {
+    f: F,
+    _entries: PhantomData<fn(K, V)>,
+}
+
+impl<K, V, F> MapEntries<K, V, F>
+where
+    F: FnMut(K, V),
+{
+    /// Creates a seed that calls `f` for each entry.
+    pub fn new(f: F) -> Self {
+        Self {
+            f,
+            _entries: PhantomData,
+        }
+    }
+}
+
+impl<'de, K, V, F> DeserializeSeed<'de> for MapEntries<K, V, F>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    F: FnMut(K, V),
+{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, K, V, F> Visitor<'de> for MapEntries<K, V, F>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    F: FnMut(K, V),
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map")
+    }
+
+    fn visit_map<A>(mut self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        while let Some((k, v)) = map.next_entry()? {
+            (self.f)(k, v);
+        }
+
+        Ok(())
+    }
+}
+
+impl<K, V, F> fmt::Debug for MapEntries<K, V, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapEntries").finish_non_exhaustive()
+    }
+}
+
+/// Deserializes a map, passing each entry to `f` as it's read, without
+/// building a map.
+///
+/// # Example
+///
+/// ```
+/// use itermap::deserialize_map_with;
+/// # use pretty_assertions::assert_eq;
+///
+/// let mut total = 0;
+///
+/// let mut deserializer = serde_json::Deserializer::from_str(r#"{"a":1,"b":2,"c":3}"#);
+/// deserialize_map_with(&mut deserializer, |_k: String, v: u32| total += v).unwrap();
+///
+/// assert_eq!(6, total);
+/// ```
+pub fn deserialize_map_with<'de, D, K, V, F>(deserializer: D, f: F) -> Result<(), D::Error>
+where
+    D: Deserializer<'de>,
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    F: FnMut(K, V),
+{
+    MapEntries::new(f).deserialize(deserializer)
+}
+
+/// Deserializes a map, extending `target` with each entry as it's read.
+///
+/// Works with any [`Extend<(K, V)>`](Extend) target, like a `Vec<(K, V)>` or
+/// an existing map.
+///
+/// # Example
+///
+/// ```
+/// use itermap::deserialize_map_into;
+/// # use pretty_assertions::assert_eq;
+///
+/// let mut entries: Vec<(String, u32)> = Vec::new();
+///
+/// let mut deserializer = serde_json::Deserializer::from_str(r#"{"a":1,"b":2}"#);
+/// deserialize_map_into(&mut deserializer, &mut entries).unwrap();
+///
+/// assert_eq!(vec![(String::from("a"), 1), (String::from("b"), 2)], entries);
+/// ```
+pub fn deserialize_map_into<'de, D, T, K, V>(
+    deserializer: D,
+    target: &mut T,
+) -> Result<(), D::Error>
+where
+    D: Deserializer<'de>,
+    T: Extend<(K, V)>,
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    deserialize_map_with(deserializer, |k, v| target.extend(Some((k, v))))
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, HashMap};
+
+    use map_vec::Map;
+    use pretty_assertions::assert_eq;
+
+    use super::{deserialize_map_into, SerializeAsMap, SerializeAsMapOnce};
+    use crate::IterMap;
+
+    #[test]
+    fn serialize() {
+        let map = BTreeMap::from([("a", 1), ("b", 2), ("c", 3)]);
+
+        let wrapped = SerializeAsMap::new(map.iter().map(|(k, v)| (k, v * 2)));
+        let json = serde_json::to_string(&wrapped).unwrap();
+        assert_eq!(r#"{"a":2,"b":4,"c":6}"#, json);
+
+        // Serializing again works, since the iterator is cloned.
+        assert_eq!(json, serde_json::to_string(&wrapped).unwrap());
+    }
+
+    #[test]
+    fn serialize_unknown_len() {
+        let items = vec![("a", 1), ("b", 2), ("c", 3)];
+
+        let json = serde_json::to_string(&SerializeAsMapOnce::new(
+            items.into_iter().filter_keys(|k| k != &"b").swap(),
+        ))
+        .unwrap();
+        assert_eq!(r#"{"1":"a","3":"c"}"#, json);
+    }
+
+    #[test]
+    fn round_trip() {
+        let map = HashMap::from([(String::from("a"), 1), (String::from("b"), 2)]);
+        let json = serde_json::to_string(&SerializeAsMap::new(map.iter())).unwrap();
+
+        let mut parsed = Map::new();
+        deserialize_map_into(&mut serde_json::Deserializer::from_str(&json), &mut parsed).unwrap();
+
+        assert_eq!(map, parsed.into_iter().collect::<HashMap<String, u32>>());
+    }
+
+    #[test]
+    fn deserialize_not_a_map() {
+        let mut entries: Vec<(String, u32)> = Vec::new();
+
+        let err = deserialize_map_into(
+            &mut serde_json::Deserializer::from_str("[1, 2]"),
+            &mut entries,
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("expected a map"), "{err}");
+    }
+}
@@ -0,0 +1,200 @@
+use alloc::vec::{self, Vec};
+use core::{fmt, iter::FusedIterator};
+
+use crate::KeyValue;
+
+/// Yields the items of a map (or any iterator of two-element tuples like
+/// `(K, V)`) in sorted order. The items are buffered when the adaptor is
+/// created.
+///
+/// See: [`IterMap::sorted_by_key`], [`IterMap::sorted_by_value`], and the
+/// related methods.
+///
+/// [`IterMap::sorted_by_key`]: crate::IterMap::sorted_by_key
+/// [`IterMap::sorted_by_value`]: crate::IterMap::sorted_by_value
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct Sorted<K, V> {
+    iter: vec::IntoIter<(K, V)>,
+}
+
+impl<K, V> Sorted<K, V> {
+    /// Buffers the items and sorts them with the provided function.
+    pub(crate) fn new<I, S>(iter: I, sort: S) -> Self
+    where
+        I: Iterator,
+        I::Item: KeyValue<Key = K, Value = V>,
+        S: FnOnce(&mut [(K, V)]),
+    {
+        let mut items: Vec<_> = iter.map(KeyValue::into_parts).collect();
+        sort(&mut items);
+
+        Self {
+            iter: items.into_iter(),
+        }
+    }
+
+    /// Returns the remaining items as a slice.
+    pub fn as_slice(&self) -> &[(K, V)] {
+        self.iter.as_slice()
+    }
+}
+
+impl<K, V> Iterator for Sorted<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter.fold(init, f)
+    }
+
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n)
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.iter.last()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Sorted<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+
+    fn rfold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter.rfold(init, f)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth_back(n)
+    }
+}
+
+impl<K, V> ExactSizeIterator for Sorted<K, V> {}
+impl<K, V> FusedIterator for Sorted<K, V> {}
+
+impl<K, V> fmt::Debug for Sorted<K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sorted")
+            .field("remaining", &self.as_slice())
+            .finish()
+    }
+}
+
+impl<K, V> Clone for Sorted<K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::IterMap;
+
+    fn map() -> HashMap<&'static str, u32> {
+        HashMap::from([("d", 1), ("b", 4), ("e", 2), ("a", 5), ("c", 3)])
+    }
+
+    #[test]
+    fn by_key() {
+        let expected = vec![("a", 5), ("b", 4), ("c", 3), ("d", 1), ("e", 2)];
+
+        let sorted: Vec<_> = map().into_iter().sorted_by_key().collect();
+        assert_eq!(expected, sorted);
+
+        let sorted: Vec<_> = map().into_iter().sorted_unstable_by_key().collect();
+        assert_eq!(expected, sorted);
+    }
+
+    #[test]
+    fn by_value() {
+        let expected = vec![("d", 1), ("e", 2), ("c", 3), ("b", 4), ("a", 5)];
+
+        let sorted: Vec<_> = map().into_iter().sorted_by_value().collect();
+        assert_eq!(expected, sorted);
+
+        let sorted: Vec<_> = map().into_iter().sorted_unstable_by_value().collect();
+        assert_eq!(expected, sorted);
+    }
+
+    #[test]
+    fn stable() {
+        let items = vec![("b", 1), ("a", 2), ("b", 3), ("a", 4)];
+
+        let sorted: Vec<_> = items.clone().into_iter().sorted_by_key().collect();
+        assert_eq!(vec![("a", 2), ("a", 4), ("b", 1), ("b", 3)], sorted);
+
+        let sorted: Vec<_> = items.into_iter().swap().sorted_by_value().collect();
+        assert_eq!(vec![(2, "a"), (4, "a"), (1, "b"), (3, "b")], sorted);
+    }
+
+    #[test]
+    fn cached_key() {
+        let mut calls = 0;
+
+        let sorted: Vec<_> = map()
+            .into_iter()
+            .sorted_by_cached_key(|k| {
+                calls += 1;
+                core::cmp::Reverse(k.to_string())
+            })
+            .collect();
+
+        assert_eq!(5, calls);
+        assert_eq!(
+            vec![("e", 2), ("d", 1), ("c", 3), ("b", 4), ("a", 5)],
+            sorted
+        );
+
+        let sorted: Vec<_> = map()
+            .into_iter()
+            .sorted_by_cached_value(|v| v % 3)
+            .map(|(_, v)| v % 3)
+            .collect();
+
+        assert_eq!(vec![0, 1, 1, 2, 2], sorted);
+    }
+
+    #[test]
+    fn double_ended_exact_size() {
+        let sorted = map().into_iter().sorted_by_key();
+        assert_eq!(5, sorted.len());
+
+        let reversed: Vec<_> = sorted.rev().collect();
+        assert_eq!(
+            vec![("e", 2), ("d", 1), ("c", 3), ("b", 4), ("a", 5)],
+            reversed
+        );
+    }
+}
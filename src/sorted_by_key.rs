@@ -0,0 +1,534 @@
+use core::{
+    cmp::Ordering,
+    fmt,
+    iter::{DoubleEndedIterator, FusedIterator, Iterator},
+};
+
+use crate::{FilterKeys, FilterValues, KeyValue, MapValues};
+
+/// Marks an `Iterator` as yielding items in ascending order by key, so
+/// operations that rely on it (merge joins, range scans, and the like) can
+/// require it in their types.
+///
+/// Iterators from a [`BTreeMap`] convert into this with [`From`], like
+/// `SortedByKey::from(map.iter())`, with the `alloc` feature. Others can be
+/// marked with [`IterMap::assume_sorted_by_key`] or checked with
+/// [`IterMap::check_sorted_by_key`].
+///
+/// The adaptors that don't change keys or their order,
+/// [`filter_keys`](Self::filter_keys), [`filter_values`](Self::filter_values),
+/// and [`map_values`](Self::map_values), keep the marker. Any other adaptor
+/// drops it.
+///
+/// # Example
+///
+/// ```
+/// use itermap::{IterMap, SortedByKey};
+/// # use pretty_assertions::assert_eq;
+///
+/// fn first_key<I, K, V>(iter: SortedByKey<I>) -> Option<K>
+/// where
+///     I: Iterator<Item = (K, V)>,
+/// {
+///     // Sorted, so the first is the smallest.
+///     iter.map(|(k, _)| k).next()
+/// }
+///
+/// let rows = vec![("a", 1), ("b", 2), ("c", 3)];
+///
+/// let sorted = rows
+///     .into_iter()
+///     .check_sorted_by_key()
+///     .filter_values(|v| *v > 1)
+///     .map_values(|v| v * 10);
+///
+/// assert_eq!(Some("b"), first_key(sorted));
+/// ```
+///
+/// [`BTreeMap`]: std::collections::BTreeMap
+/// [`IterMap::assume_sorted_by_key`]: crate::IterMap::assume_sorted_by_key
+/// [`IterMap::check_sorted_by_key`]: crate::IterMap::check_sorted_by_key
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct SortedByKey<I> {
+    iter: I,
+}
+
+impl<I> SortedByKey<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        Self { iter }
+    }
+
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items, which leaves the rest
+    /// sorted.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the marker, returning the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+
+    /// Like [`IterMap::filter_keys`](crate::IterMap::filter_keys), keeping
+    /// the marker.
+    pub fn filter_keys<K, V, Fk>(self, key_op: Fk) -> SortedByKey<FilterKeys<I, Fk>>
+    where
+        I: Iterator,
+        I::Item: KeyValue<Key = K, Value = V>,
+        Fk: FnMut(&K) -> bool,
+    {
+        SortedByKey::new(FilterKeys::new(self.iter, key_op))
+    }
+
+    /// Like [`IterMap::filter_values`](crate::IterMap::filter_values),
+    /// keeping the marker.
+    pub fn filter_values<K, V, Fv>(self, value_op: Fv) -> SortedByKey<FilterValues<I, Fv>>
+    where
+        I: Iterator,
+        I::Item: KeyValue<Key = K, Value = V>,
+        Fv: FnMut(&V) -> bool,
+    {
+        SortedByKey::new(FilterValues::new(self.iter, value_op))
+    }
+
+    /// Like [`IterMap::map_values`](crate::IterMap::map_values), keeping the
+    /// marker.
+    pub fn map_values<K, V, Fv, W>(self, value_op: Fv) -> SortedByKey<MapValues<I, Fv>>
+    where
+        I: Iterator,
+        I::Item: KeyValue<Key = K, Value = V>,
+        Fv: FnMut(V) -> W,
+    {
+        SortedByKey::new(MapValues::new(self.iter, value_op))
+    }
+}
+
+impl<I> Iterator for SortedByKey<I>
+where
+    I: Iterator,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter.fold(init, f)
+    }
+
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n)
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.iter.last()
+    }
+}
+
+impl<I> DoubleEndedIterator for SortedByKey<I>
+where
+    I: DoubleEndedIterator,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+
+    fn rfold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter.rfold(init, f)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth_back(n)
+    }
+}
+
+impl<I> ExactSizeIterator for SortedByKey<I> where I: ExactSizeIterator {}
+impl<I> FusedIterator for SortedByKey<I> where I: FusedIterator {}
+
+impl<I> fmt::Debug for SortedByKey<I>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SortedByKey")
+            .field("iter", &self.iter)
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod btree {
+    use alloc::collections::btree_map;
+
+    use super::SortedByKey;
+
+    macro_rules! from_btree_map {
+        ($($iter:ident),+) => {
+            $(
+                impl<'a, K, V> From<btree_map::$iter<'a, K, V>>
+                    for SortedByKey<btree_map::$iter<'a, K, V>>
+                {
+                    fn from(iter: btree_map::$iter<'a, K, V>) -> Self {
+                        Self::new(iter)
+                    }
+                }
+            )+
+        };
+    }
+
+    from_btree_map!(Iter, IterMut, Range, RangeMut);
+
+    impl<K, V> From<btree_map::IntoIter<K, V>> for SortedByKey<btree_map::IntoIter<K, V>> {
+        fn from(iter: btree_map::IntoIter<K, V>) -> Self {
+            Self::new(iter)
+        }
+    }
+}
+
+/// Checks that items are in ascending order by key as they're yielded,
+/// panicking on the first that isn't.
+///
+/// See [`VerifySorted`] for yielding an error instead.
+///
+/// See: [`IterMap::check_sorted_by_key`]
+///
+/// [`IterMap::check_sorted_by_key`]: crate::IterMap::check_sorted_by_key
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct CheckSorted<I, K> {
+    iter: I,
+    last: Option<K>,
+}
+
+impl<I, K> CheckSorted<I, K> {
+    pub(crate) fn new(iter: I) -> Self {
+        Self { iter, last: None }
+    }
+
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items in this adaptor, so they
+    /// aren't checked.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the adaptor, returning the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I, K, V> Iterator for CheckSorted<I, K>
+where
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: PartialOrd + Clone + fmt::Debug,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+
+        if let Err(previous) = check(&mut self.last, &item) {
+            panic!(
+                "items are not sorted by key: {:?} came after {previous:?}",
+                item.with_key(K::clone),
+            );
+        }
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, K, V> ExactSizeIterator for CheckSorted<I, K>
+where
+    I: ExactSizeIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: PartialOrd + Clone + fmt::Debug,
+{
+}
+
+impl<I, K, V> FusedIterator for CheckSorted<I, K>
+where
+    I: FusedIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: PartialOrd + Clone + fmt::Debug,
+{
+}
+
+impl<I, K> fmt::Debug for CheckSorted<I, K>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CheckSorted")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Checks that items are in ascending order by key as they're yielded,
+/// yielding an [`Unsorted`] error for each that isn't.
+///
+/// See: [`IterMap::verify_sorted_by_key`]
+///
+/// [`IterMap::verify_sorted_by_key`]: crate::IterMap::verify_sorted_by_key
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct VerifySorted<I, K> {
+    iter: I,
+    last: Option<K>,
+}
+
+impl<I, K> VerifySorted<I, K> {
+    pub(crate) fn new(iter: I) -> Self {
+        Self { iter, last: None }
+    }
+
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items in this adaptor, so they
+    /// aren't checked.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the adaptor, returning the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I, K, V> Iterator for VerifySorted<I, K>
+where
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: PartialOrd + Clone,
+{
+    type Item = Result<I::Item, Unsorted<I::Item, K>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+
+        Some(match check(&mut self.last, &item) {
+            Ok(()) => Ok(item),
+            Err(previous) => Err(Unsorted { item, previous }),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, K, V> ExactSizeIterator for VerifySorted<I, K>
+where
+    I: ExactSizeIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: PartialOrd + Clone,
+{
+}
+
+impl<I, K, V> FusedIterator for VerifySorted<I, K>
+where
+    I: FusedIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+    K: PartialOrd + Clone,
+{
+}
+
+impl<I, K> fmt::Debug for VerifySorted<I, K>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VerifySorted")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An item with a key less than a key before it, from
+/// [`IterMap::verify_sorted_by_key`].
+///
+/// [`IterMap::verify_sorted_by_key`]: crate::IterMap::verify_sorted_by_key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsorted<T, K> {
+    item: T,
+    previous: K,
+}
+
+impl<T, K> Unsorted<T, K> {
+    /// The item that's out of order.
+    pub fn item(&self) -> &T {
+        &self.item
+    }
+
+    /// The greatest key before the item, which its key is less than.
+    pub fn previous_key(&self) -> &K {
+        &self.previous
+    }
+
+    /// Consumes the error, returning the item that's out of order.
+    pub fn into_item(self) -> T {
+        self.item
+    }
+}
+
+impl<T, K> fmt::Display for Unsorted<T, K>
+where
+    T: KeyValue<Key = K>,
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.item.with_key(|key| {
+            write!(
+                f,
+                "items are not sorted by key: {key:?} came after {:?}",
+                self.previous,
+            )
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, K> std::error::Error for Unsorted<T, K>
+where
+    T: KeyValue<Key = K> + fmt::Debug,
+    K: fmt::Debug,
+{
+}
+
+/// Checks the item's key against `last`, the greatest key so far, returning
+/// that key if the item's is less or can't be compared to it. Otherwise, the
+/// item's key becomes `last`.
+fn check<T, K>(last: &mut Option<K>, item: &T) -> Result<(), K>
+where
+    T: KeyValue<Key = K>,
+    K: PartialOrd + Clone,
+{
+    item.with_key(|key| match last {
+        Some(last) if !K::partial_cmp(last, key).is_some_and(Ordering::is_le) => Err(last.clone()),
+        _ => {
+            *last = Some(key.clone());
+            Ok(())
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::Unsorted;
+    use crate::IterMap;
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn btree_map() {
+        use std::collections::BTreeMap;
+
+        use super::SortedByKey;
+
+        let mut map = BTreeMap::from([('c', 3), ('a', 1), ('b', 2)]);
+
+        let sorted: SortedByKey<_> = SortedByKey::from(map.iter())
+            .filter_keys(|k| k != &&'b')
+            .map_values(|v| v * 10);
+        assert_eq!(vec![(&'a', 10), (&'c', 30)], sorted.collect::<Vec<_>>());
+
+        SortedByKey::from(map.iter_mut()).for_each(|(_, v)| *v += 1);
+        SortedByKey::from(map.range('b'..)).for_each(|(_, v)| assert!(v > &2));
+
+        let sorted = SortedByKey::from(map.into_iter()).filter_values(|v| v % 2 == 0);
+        assert_eq!(vec![('a', 2), ('c', 4)], sorted.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn check() {
+        let items = vec![(1, 'a'), (2, 'b'), (2, 'c'), (5, 'd')];
+
+        let checked: Vec<_> = items.clone().into_iter().check_sorted_by_key().collect();
+        assert_eq!(items, checked);
+
+        let assumed = items.clone().into_iter().assume_sorted_by_key();
+        assert_eq!(items, assumed.into_inner().collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "items are not sorted by key: 2 came after 3")]
+    fn check_unsorted() {
+        vec![(1, 'a'), (3, 'b'), (2, 'c')]
+            .into_iter()
+            .check_sorted_by_key()
+            .for_each(drop);
+    }
+
+    #[test]
+    fn verify() {
+        let results: Vec<_> = vec![(1, 'a'), (3, 'b'), (2, 'c'), (3, 'd'), (4, 'e')]
+            .into_iter()
+            .verify_sorted_by_key()
+            .collect();
+
+        // Later keys are compared to the greatest key so far.
+        assert_eq!(
+            vec![
+                Ok((1, 'a')),
+                Ok((3, 'b')),
+                Err(Unsorted {
+                    item: (2, 'c'),
+                    previous: 3,
+                }),
+                Ok((3, 'd')),
+                Ok((4, 'e')),
+            ],
+            results
+        );
+
+        let err = results[2].clone().unwrap_err();
+        assert_eq!(
+            "items are not sorted by key: 2 came after 3",
+            err.to_string()
+        );
+        assert_eq!(&3, err.previous_key());
+        assert_eq!((2, 'c'), err.into_item());
+    }
+}
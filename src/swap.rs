@@ -1,5 +1,14 @@
 use core::{fmt, iter::FusedIterator};
 
+use crate::KeyValue;
+
+/// Swaps the elements of two-element tuples (like `(K, V)`), yielding
+/// `(V, K)`.
+///
+/// See: [`IterMap::swap`]
+///
+/// [`IterMap::swap`]: crate::IterMap::swap
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
 pub struct Swap<I> {
     iter: I,
 }
@@ -9,33 +18,102 @@ impl<I> Swap<I> {
         Self { iter }
     }
 
-    fn map_item<K, V>((k, v): (K, V)) -> (V, K) {
+    /// Returns a reference to the underlying iterator.
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Returns a mutable reference to the underlying iterator.
+    ///
+    /// Advancing it directly skips those items in this adaptor.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Consumes the adaptor, returning the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+
+    fn map_item<T>(item: T) -> (T::Value, T::Key)
+    where
+        T: KeyValue,
+    {
+        let (k, v) = item.into_parts();
         (v, k)
     }
 }
 
 impl<I, K, V> Iterator for Swap<I>
 where
-    I: Iterator<Item = (K, V)>,
+    I: Iterator,
+    I::Item: KeyValue<Key = K, Value = V>,
 {
     type Item = (V, K);
 
     fn next(&mut self) -> Option<Self::Item> {
         self.iter.next().map(Self::map_item)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter
+            .fold(init, move |acc, item| f(acc, Self::map_item(item)))
+    }
+
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n).map(Self::map_item)
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.iter.last().map(Self::map_item)
+    }
 }
 
 impl<I, K, V> DoubleEndedIterator for Swap<I>
 where
-    I: DoubleEndedIterator<Item = (K, V)>,
+    I: DoubleEndedIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.iter.next_back().map(Self::map_item)
     }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter
+            .rfold(init, move |acc, item| f(acc, Self::map_item(item)))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth_back(n).map(Self::map_item)
+    }
 }
 
-impl<I, K, V> ExactSizeIterator for Swap<I> where I: Iterator<Item = (K, V)> + ExactSizeIterator {}
-impl<I, K, V> FusedIterator for Swap<I> where I: Iterator<Item = (K, V)> + FusedIterator {}
+impl<I, K, V> ExactSizeIterator for Swap<I>
+where
+    I: Iterator + ExactSizeIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+{
+}
+impl<I, K, V> FusedIterator for Swap<I>
+where
+    I: Iterator + FusedIterator,
+    I::Item: KeyValue<Key = K, Value = V>,
+{
+}
 
 impl<I> fmt::Debug for Swap<I>
 where
@@ -58,3 +136,37 @@ where
 }
 
 impl<I> Copy for Swap<I> where I: Copy {}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::IterMap;
+
+    #[test]
+    fn exact_size() {
+        let iter = vec![("a", 1), ("b", 2), ("c", 3)].into_iter().swap();
+
+        assert_eq!((3, Some(3)), iter.size_hint());
+        assert_eq!(3, iter.len());
+    }
+
+    #[test]
+    fn internal_iteration() {
+        let original = vec![("a", 1), ("b", 2), ("c", 3), ("d", 4)];
+        let swapped = || original.clone().into_iter().swap();
+
+        assert_eq!(4, swapped().count());
+        assert_eq!(Some((4, "d")), swapped().last());
+        assert_eq!(Some((2, "b")), swapped().nth(1));
+        assert_eq!(Some((3, "c")), swapped().nth_back(1));
+        assert_eq!(
+            "1a2b3c4d",
+            swapped().fold(String::new(), |acc, (v, k)| format!("{acc}{v}{k}"))
+        );
+        assert_eq!(
+            "4d3c2b1a",
+            swapped().rfold(String::new(), |acc, (v, k)| format!("{acc}{v}{k}"))
+        );
+    }
+}
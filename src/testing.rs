@@ -0,0 +1,340 @@
+//! Functions backing the assertion macros enabled by the `testing` feature.
+//! Use the macros instead of calling these directly.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Write};
+
+/// Asserts that two `Iterator`s over two-element tuples (like `(K, V)`) have
+/// the same items, ignoring order.
+///
+/// Duplicate items are counted, so `[(1, 'a'), (1, 'a')]` doesn't equal
+/// `[(1, 'a')]`. Only needs the keys and values to be [`PartialEq`] and
+/// [`Debug`](core::fmt::Debug). On failure, the differences are listed key by
+/// key, with `<` for items only on the left and `>` for items only on the
+/// right.
+///
+/// Requires the `testing` feature.
+///
+/// # Example
+///
+/// ```
+/// # use std::collections::HashMap;
+/// #
+/// use itermap::{assert_pairs_eq_unordered, IterMap};
+///
+/// let map = HashMap::from([("a", 1), ("b", 2)]);
+///
+/// assert_pairs_eq_unordered!(
+///     map.into_iter().map_values(|v| v * 10),
+///     [("b", 20), ("a", 10)],
+/// );
+/// ```
+///
+/// ```should_panic
+/// use itermap::assert_pairs_eq_unordered;
+///
+/// // Panics with:
+/// //
+/// // pairs are not equal, ignoring order
+/// // key "b":
+/// // < 2
+/// // > 3
+/// assert_pairs_eq_unordered!([("a", 1), ("b", 2)], [("a", 1), ("b", 3)]);
+/// ```
+#[macro_export]
+macro_rules! assert_pairs_eq_unordered {
+    ($left:expr, $right:expr $(,)?) => {
+        if let Err(diff) = $crate::testing::pairs_eq_unordered($left, $right) {
+            panic!("{}", diff);
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        if let Err(diff) = $crate::testing::pairs_eq_unordered($left, $right) {
+            panic!("{}: {}", format_args!($($arg)+), diff);
+        }
+    };
+}
+
+/// Asserts that every item of the first `Iterator` over two-element tuples
+/// (like `(K, V)`) is also an item of the second, ignoring order.
+///
+/// Duplicate items are counted, so `[(1, 'a'), (1, 'a')]` isn't a submap of
+/// `[(1, 'a')]`. On failure, the missing items are listed key by key, with
+/// `<` for items missing from the second iterator and `>` for the values it
+/// has for that key instead.
+///
+/// Requires the `testing` feature.
+///
+/// # Example
+///
+/// ```
+/// # use std::collections::HashMap;
+/// #
+/// use itermap::assert_is_submap;
+///
+/// let map = HashMap::from([("a", 1), ("b", 2), ("c", 3)]);
+///
+/// assert_is_submap!([("c", 3), ("a", 1)], map);
+/// ```
+#[macro_export]
+macro_rules! assert_is_submap {
+    ($sub:expr, $sup:expr $(,)?) => {
+        if let Err(diff) = $crate::testing::is_submap($sub, $sup) {
+            panic!("{}", diff);
+        }
+    };
+    ($sub:expr, $sup:expr, $($arg:tt)+) => {
+        if let Err(diff) = $crate::testing::is_submap($sub, $sup) {
+            panic!("{}: {}", format_args!($($arg)+), diff);
+        }
+    };
+}
+
+/// Asserts that two `Iterator`s over two-element tuples (like `(K, V)`) have
+/// the same keys, ignoring order and values.
+///
+/// Duplicate keys are counted. On failure, the keys only on the left are
+/// listed with `<`, and those only on the right with `>`.
+///
+/// Requires the `testing` feature.
+///
+/// # Example
+///
+/// ```
+/// # use std::collections::HashMap;
+/// #
+/// use itermap::assert_same_keys;
+///
+/// let before = HashMap::from([("a", 1), ("b", 2)]);
+/// let after = vec![("b", "two"), ("a", "one")];
+///
+/// assert_same_keys!(before, after);
+/// ```
+#[macro_export]
+macro_rules! assert_same_keys {
+    ($left:expr, $right:expr $(,)?) => {
+        if let Err(diff) = $crate::testing::same_keys($left, $right) {
+            panic!("{}", diff);
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        if let Err(diff) = $crate::testing::same_keys($left, $right) {
+            panic!("{}: {}", format_args!($($arg)+), diff);
+        }
+    };
+}
+
+/// Checks that `left` and `right` have the same items, ignoring order.
+pub fn pairs_eq_unordered<L, R, K, V>(left: L, right: R) -> Result<(), String>
+where
+    L: IntoIterator<Item = (K, V)>,
+    R: IntoIterator<Item = (K, V)>,
+    K: PartialEq + fmt::Debug,
+    V: PartialEq + fmt::Debug,
+{
+    let (left, right) = unmatched(left, right, |a, b| a == b);
+
+    if left.is_empty() && right.is_empty() {
+        return Ok(());
+    }
+
+    Err(diff("pairs are not equal, ignoring order", left, right))
+}
+
+/// Checks that every item in `sub` is in `sup`, ignoring order.
+pub fn is_submap<S, T, K, V>(sub: S, sup: T) -> Result<(), String>
+where
+    S: IntoIterator<Item = (K, V)>,
+    T: IntoIterator<Item = (K, V)>,
+    K: PartialEq + fmt::Debug,
+    V: PartialEq + fmt::Debug,
+{
+    let (missing, extra) = unmatched(sub, sup, |a, b| a == b);
+
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    // Only show the other values for the keys that are missing items.
+    let extra = extra
+        .into_iter()
+        .filter(|(k, _)| missing.iter().any(|(m, _)| m == k))
+        .collect();
+
+    Err(diff("left is not a submap of right", missing, extra))
+}
+
+/// Checks that `left` and `right` have the same keys, ignoring order.
+pub fn same_keys<L, R, K, V, W>(left: L, right: R) -> Result<(), String>
+where
+    L: IntoIterator<Item = (K, V)>,
+    R: IntoIterator<Item = (K, W)>,
+    K: PartialEq + fmt::Debug,
+{
+    let left = left.into_iter().map(|(k, _)| (k, ()));
+    let right = right.into_iter().map(|(k, _)| (k, ()));
+    let (left, right) = unmatched(left, right, |a, b| a == b);
+
+    if left.is_empty() && right.is_empty() {
+        return Ok(());
+    }
+
+    let mut out = String::from("keys are not the same, ignoring order");
+    for (k, _) in &left {
+        let _ = write!(out, "\n< {k:?}");
+    }
+    for (k, _) in &right {
+        let _ = write!(out, "\n> {k:?}");
+    }
+
+    Err(out)
+}
+
+/// Pairs up equal items from `left` and `right`, returning those that didn't
+/// have a match on the other side.
+fn unmatched<L, R, T, F>(left: L, right: R, mut eq: F) -> (Vec<T>, Vec<T>)
+where
+    L: IntoIterator<Item = T>,
+    R: IntoIterator<Item = T>,
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut right: Vec<_> = right.into_iter().collect();
+
+    let left = left
+        .into_iter()
+        .filter(|l| match right.iter().position(|r| eq(l, r)) {
+            Some(i) => {
+                right.swap_remove(i);
+                false
+            }
+            None => true,
+        })
+        .collect();
+
+    (left, right)
+}
+
+/// Lists the unmatched items key by key, in the order the keys were first
+/// seen.
+fn diff<K, V>(title: &str, left: Vec<(K, V)>, right: Vec<(K, V)>) -> String
+where
+    K: PartialEq + fmt::Debug,
+    V: fmt::Debug,
+{
+    let mut keys: Vec<&K> = Vec::new();
+    for (k, _) in left.iter().chain(&right) {
+        if !keys.contains(&k) {
+            keys.push(k);
+        }
+    }
+
+    let mut out = String::from(title);
+    for key in keys {
+        let _ = write!(out, "\nkey {key:?}:");
+
+        for (_, v) in left.iter().filter(|(k, _)| k == key) {
+            let _ = write!(out, "\n< {v:?}");
+        }
+        for (_, v) in right.iter().filter(|(k, _)| k == key) {
+            let _ = write!(out, "\n> {v:?}");
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use pretty_assertions::assert_eq;
+
+    use super::{is_submap, pairs_eq_unordered, same_keys};
+
+    #[test]
+    fn eq_unordered() {
+        let map = HashMap::from([("a", 1), ("b", 2), ("c", 3)]);
+
+        assert_pairs_eq_unordered!(map.clone(), [("c", 3), ("a", 1), ("b", 2)]);
+        assert_pairs_eq_unordered!(
+            map.iter(),
+            map.iter().collect::<Vec<_>>(),
+            "{} items",
+            map.len()
+        );
+
+        assert_eq!(
+            Err(String::from(
+                "\
+pairs are not equal, ignoring order
+key \"b\":
+< 2
+> 20
+key \"d\":
+> 4"
+            )),
+            pairs_eq_unordered(
+                [("a", 1), ("b", 2), ("c", 3)],
+                [("c", 3), ("b", 20), ("a", 1), ("d", 4)]
+            )
+        );
+    }
+
+    #[test]
+    fn duplicates() {
+        assert_eq!(
+            Err(String::from(
+                "\
+pairs are not equal, ignoring order
+key 'x':
+< 1"
+            )),
+            pairs_eq_unordered([('x', 1), ('x', 1)], [('x', 1)])
+        );
+
+        assert!(is_submap([('x', 1), ('x', 1)], [('x', 1)]).is_err());
+        assert!(same_keys([('x', 1), ('x', 1)], [('x', "one")]).is_err());
+    }
+
+    #[test]
+    fn submap() {
+        let map = HashMap::from([("a", 1), ("b", 2), ("c", 3)]);
+
+        assert_is_submap!([("b", 2)], map.clone());
+        assert_is_submap!(Vec::new(), map.clone());
+
+        assert_eq!(
+            Err(String::from(
+                "\
+left is not a submap of right
+key \"b\":
+< 20
+> 2
+key \"z\":
+< 0"
+            )),
+            is_submap([("a", 1), ("b", 20), ("z", 0)], map)
+        );
+    }
+
+    #[test]
+    fn keys() {
+        assert_same_keys!([("a", 1), ("b", 2)], [("b", 'x'), ("a", 'y')]);
+
+        assert_eq!(
+            Err(String::from(
+                "\
+keys are not the same, ignoring order
+< \"a\"
+> \"c\""
+            )),
+            same_keys([("a", 1), ("b", 2)], [("b", 'x'), ("c", 'y')])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "context 1: pairs are not equal, ignoring order")]
+    fn message() {
+        assert_pairs_eq_unordered!([(1, 1)], [(1, 2)], "context {}", 1);
+    }
+}
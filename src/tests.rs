@@ -108,3 +108,83 @@
 /// ```
 #[cfg(doctest)]
 pub struct Only2ElementTuples;
+
+/// For testing the errors from `#[derive(KeyValue)]`.
+///
+/// ----
+///
+/// No key field
+/// ``` compile_fail
+/// #[derive(itermap::KeyValue)]
+/// struct Header {
+///     name: String,
+///     value: String,
+/// }
+/// ```
+///
+/// More than two fields, with no value field
+/// ``` compile_fail
+/// #[derive(itermap::KeyValue)]
+/// struct Header {
+///     #[kv(key)]
+///     name: String,
+///     value: String,
+///     sensitive: bool,
+/// }
+/// ```
+///
+/// Only a key field
+/// ``` compile_fail
+/// #[derive(itermap::KeyValue)]
+/// struct Header {
+///     #[kv(key)]
+///     name: String,
+/// }
+/// ```
+///
+/// Two key fields
+/// ``` compile_fail
+/// #[derive(itermap::KeyValue)]
+/// struct Header {
+///     #[kv(key)]
+///     name: String,
+///     #[kv(key)]
+///     value: String,
+/// }
+/// ```
+///
+/// Unknown attribute
+/// ``` compile_fail
+/// #[derive(itermap::KeyValue)]
+/// struct Header {
+///     #[kv(id)]
+///     name: String,
+///     value: String,
+/// }
+/// ```
+///
+/// Not a struct
+/// ``` compile_fail
+/// #[derive(itermap::KeyValue)]
+/// enum Header {
+///     Host(String),
+/// }
+/// ```
+///
+/// No `FromKeyValue` with other fields, even if they're `Default`
+/// ``` compile_fail
+/// use itermap::FromKeyValue;
+///
+/// #[derive(itermap::KeyValue)]
+/// struct Header {
+///     #[kv(key)]
+///     name: String,
+///     #[kv(value)]
+///     value: String,
+///     sensitive: bool,
+/// }
+///
+/// Header::from_parts(String::from("Host"), String::from("example.com"));
+/// ```
+#[cfg(all(doctest, feature = "derive"))]
+pub struct DeriveKeyValueErrors;
@@ -0,0 +1,66 @@
+#![cfg(feature = "derive")]
+
+use std::collections::BTreeMap;
+
+use itermap::{FromKeyValue, IterMap, KeyValue};
+use pretty_assertions::assert_eq;
+
+#[derive(Clone, Debug, PartialEq, KeyValue)]
+struct Header {
+    #[kv(key)]
+    name: &'static str,
+    value: &'static str,
+}
+
+#[derive(Debug, PartialEq, KeyValue)]
+struct Record {
+    id: u32,
+    #[kv(key)]
+    name: String,
+    #[kv(value)]
+    score: u32,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, KeyValue)]
+struct Tuple<T>(T, #[kv(key)] char);
+
+#[test]
+fn two_fields() {
+    let header = Header::from_parts("Host", "example.com");
+    assert_eq!("Host", header.with_key(|name| *name));
+    assert_eq!(11, header.with_value(|value| value.len()));
+    assert_eq!(("Host", "example.com"), header.clone().into_parts());
+
+    let headers = vec![header, Header::from_parts("Accept", "text/html")];
+    let hosts: Vec<Header> = headers
+        .clone()
+        .into_iter()
+        .filter_keys(|name| name.eq_ignore_ascii_case("host"))
+        .collect();
+    assert_eq!(vec![Header::from_parts("Host", "example.com")], hosts);
+
+    let lengths: BTreeMap<_, _> = headers.into_iter().map_values(str::len).collect();
+    assert_eq!(BTreeMap::from([("Accept", 9), ("Host", 11)]), lengths);
+}
+
+#[test]
+fn extra_fields() {
+    let record = Record {
+        id: 7,
+        name: String::from("a"),
+        score: 10,
+        tags: vec![String::from("x")],
+    };
+    assert_eq!(String::from("a"), record.with_key(String::clone));
+    assert_eq!(10, record.with_value(|score| *score));
+    assert_eq!((String::from("a"), 10), record.into_parts());
+}
+
+#[test]
+fn tuple_struct() {
+    let items = vec![Tuple(1, 'a'), Tuple(2, 'b')];
+
+    assert_eq!(Some(2), items.into_iter().get(&'b'));
+    assert_eq!(Tuple("x", 'k'), Tuple::from_parts('k', "x"));
+}